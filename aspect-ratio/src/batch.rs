@@ -0,0 +1,72 @@
+//! Batch reduction of ratios read one per line.
+
+use serde::Serialize;
+use std::io::{self, BufRead, Write};
+
+use crate::parse::ParseError;
+use crate::reduce_input;
+
+#[derive(Serialize)]
+struct BatchResult<'a> {
+    input: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ratio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Reduces a line in any of the forms `calc` accepts: `1920x1080`, `16:9`
+/// or `1920 1080`
+fn reduce_line(line: &str) -> Result<(u32, u32), ParseError> {
+    let mut parts = line.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(ratio), None, None) => reduce_input(ratio, None),
+        (Some(width), Some(height), None) => reduce_input(width, Some(height)),
+        _ => Err(ParseError::InvalidFormat),
+    }
+}
+
+/// Reduces every non-blank line, writing one result per line or a JSON
+/// array. Lines that fail are reported on stderr in plain mode and as
+/// `error` entries in JSON. Returns the number of failed lines.
+pub fn run<R: BufRead, W: Write>(reader: R, out: &mut W, json: bool) -> io::Result<usize> {
+    let mut failed: usize = 0;
+    let mut first: bool = true;
+    if json {
+        write!(out, "[")?;
+    }
+    for (index, line) in reader.lines().enumerate() {
+        let line: String = line?;
+        let input: &str = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+        let result = reduce_line(input);
+        if result.is_err() {
+            failed += 1;
+        }
+
+        if json {
+            let entry = BatchResult {
+                input,
+                ratio: result.as_ref().ok().map(|(w, h)| format!("{}:{}", w, h)),
+                error: result.as_ref().err().map(ToString::to_string),
+            };
+            if !first {
+                write!(out, ",")?;
+            }
+            write!(out, "\n  {}", serde_json::to_string(&entry)?)?;
+            first = false;
+        } else {
+            match result {
+                Ok((w, h)) => writeln!(out, "{}:{}", w, h)?,
+                Err(e) => eprintln!("line {}: {}: {}", index + 1, e, input),
+            }
+        }
+    }
+    if json {
+        writeln!(out, "{}]", if first { "" } else { "\n" })?;
+    }
+    out.flush()?;
+    Ok(failed)
+}
@@ -1,9 +1,17 @@
 use clap::CommandFactory;
-use clap::{Args as ClapArgs, Parser, Subcommand};
+use clap::{ArgGroup, Args as ClapArgs, Parser, Subcommand};
 use clap_complete::generate;
 use std::error::Error;
-use std::fmt;
-use std::io;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+
+use parse::{check_dimensions, parse_ratio, ParseError, DEFAULT_TOLERANCE_PERCENT, MAX_VALUE};
+
+mod batch;
+mod parse;
+mod resolutions;
+mod standard;
 
 // Simple aspect ratio calculator
 #[derive(Parser, Debug)]
@@ -19,14 +27,16 @@ enum Commands {
     #[command(
         about = "Convert an aspect ratio to a target width or height.",
         long_about = "Convert an aspect ratio to a target width or height.\n\
-Examples:\n  aspect-ratio convert 16:9 --width 1920\n  aspect-ratio convert 4:3 --height 720"
+Examples:\n  aspect-ratio convert 16:9 --width 1920\n  aspect-ratio convert 4:3 --height 720\n  \
+aspect-ratio convert 2.35 --width 1920"
     )]
     Convert(ConvertArgs),
     /// Show info about an aspect ratio
     #[command(
         about = "Show info about an aspect ratio.",
         long_about = "Show info about an aspect ratio.\n\
-Examples:\n  aspect-ratio info 1920x1080\n  aspect-ratio info 4:3"
+Examples:\n  aspect-ratio info 1920x1080\n  aspect-ratio info 4:3\n  aspect-ratio info 1.777\n  \
+aspect-ratio info 56.25%"
     )]
     Info(InfoArgs),
     /// Generate shell completions
@@ -45,14 +55,24 @@ Supported shells: bash, zsh, fish, powershell, elvish"
     #[command(
         about = "Reduce an aspect ratio.",
         long_about = "Reduce an aspect ratio to its simplest form.\n\
-Examples:\n  aspect-ratio calc 1920x1080\n  aspect-ratio calc 16:9\n  aspect-ratio calc 1920 1080"
+Examples:\n  aspect-ratio calc 1920x1080\n  aspect-ratio calc 16:9\n  aspect-ratio calc 1920 1080\n  \
+aspect-ratio calc --file resolutions.txt --json"
     )]
     Calc(CalcArgs),
+    /// List resolutions for an aspect ratio
+    #[command(
+        about = "List resolutions for an aspect ratio.",
+        long_about = "List the canonical resolutions (720p, 1080p, 1440p, 4K, 8K) for an aspect ratio, \
+or every multiple of it with --custom-step. Bounds apply to the short edge.\n\
+Examples:\n  aspect-ratio resolutions 16:9\n  aspect-ratio resolutions 21:9 --min 1080\n  \
+aspect-ratio resolutions 16:9 --custom-step 8 --min 720 --max 1080"
+    )]
+    Resolutions(ResolutionsArgs),
 }
 
 #[derive(ClapArgs, Debug)]
 struct ConvertArgs {
-    /// Aspect ratio, e.g. 16:9 or 1920x1080
+    /// Aspect ratio, e.g. 16:9, 1920x1080, 2.35 or 56.25%
     ratio: String,
     /// Target width (optional)
     #[arg(long)]
@@ -60,20 +80,52 @@ struct ConvertArgs {
     /// Target height (optional)
     #[arg(long)]
     height: Option<u32>,
+    /// Allowed error in percent when approximating a decimal or percentage ratio
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_TOLERANCE_PERCENT, value_parser = parse::parse_tolerance)]
+    tolerance: f64,
 }
 
 #[derive(ClapArgs, Debug)]
 struct InfoArgs {
-    /// Aspect ratio, e.g. 16:9 or 1920x1080
+    /// Aspect ratio, e.g. 16:9, 1920x1080, 2.35 or 56.25%
     ratio: String,
+    /// Allowed error in percent when approximating a decimal or percentage ratio
+    #[arg(long, value_name = "PERCENT", default_value_t = DEFAULT_TOLERANCE_PERCENT, value_parser = parse::parse_tolerance)]
+    tolerance: f64,
 }
 
 #[derive(ClapArgs, Debug)]
+#[command(group(ArgGroup::new("batch").args(["stdin", "file"])))]
 struct CalcArgs {
     /// Aspect ratio (e.g. 1920x1080, 16:9) or width
-    arg1: String,
+    #[arg(required_unless_present = "batch")]
+    arg1: Option<String>,
     /// Height (optional, if arg1 is width)
     arg2: Option<String>,
+    /// Read one ratio per line from standard input
+    #[arg(long, conflicts_with_all = ["arg1", "file"])]
+    stdin: bool,
+    /// Read one ratio per line from a file
+    #[arg(long, value_name = "PATH", conflicts_with = "arg1")]
+    file: Option<PathBuf>,
+    /// Print batch results as a JSON array
+    #[arg(long, requires = "batch")]
+    json: bool,
+}
+
+#[derive(ClapArgs, Debug)]
+struct ResolutionsArgs {
+    /// Aspect ratio, e.g. 16:9 or 1920x1080
+    ratio: String,
+    /// Smallest short edge to list, in pixels
+    #[arg(long, value_name = "PIXELS")]
+    min: Option<u32>,
+    /// Largest short edge to list, in pixels
+    #[arg(long, value_name = "PIXELS")]
+    max: Option<u32>,
+    /// List every N-th exact multiple of the reduced ratio instead of the canonical tiers
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    custom_step: Option<u32>,
 }
 
 mod aspect_ratio {
@@ -94,46 +146,6 @@ mod aspect_ratio {
     }
 }
 
-#[derive(Debug)]
-enum ParseError {
-    InvalidFormat,
-    InvalidNumbers,
-    NonPositiveNumbers,
-    TooLarge,
-}
-
-impl fmt::Display for ParseError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            ParseError::InvalidFormat => write!(f, "Invalid input format"),
-            ParseError::InvalidNumbers => write!(f, "Invalid numbers provided"),
-            ParseError::NonPositiveNumbers => {
-                write!(f, "Width and height must be positive numbers")
-            }
-            ParseError::TooLarge => write!(f, "Numbers are too large"),
-        }
-    }
-}
-
-impl Error for ParseError {}
-
-fn parse_ratio(input: &str) -> Result<(u32, u32), ParseError> {
-    let (w, h) = if let Some((w, h)) = input.split_once(['x', ':', '×']) {
-        (w.trim(), h.trim())
-    } else {
-        return Err(ParseError::InvalidFormat);
-    };
-    let width: u32 = w.parse().map_err(|_| ParseError::InvalidNumbers)?;
-    let height: u32 = h.parse().map_err(|_| ParseError::InvalidNumbers)?;
-    if width == 0 || height == 0 {
-        return Err(ParseError::NonPositiveNumbers);
-    }
-    if width > 100_000_000 || height > 100_000_000 {
-        return Err(ParseError::TooLarge);
-    }
-    Ok((width, height))
-}
-
 #[derive(Debug, Clone, Copy)]
 enum SupportedShell {
     Bash,
@@ -169,9 +181,9 @@ impl SupportedShell {
 }
 
 fn handle_convert(args: &ConvertArgs) -> Result<(), Box<dyn Error>> {
-    let (w, h) = parse_ratio(&args.ratio)?;
+    let (w, h) = parse_ratio(&args.ratio, args.tolerance)?;
     if let Some(width) = args.width {
-        if width > 100_000_000 {
+        if width > MAX_VALUE {
             return Err(Box::new(ParseError::TooLarge));
         }
         let height = width
@@ -180,7 +192,7 @@ fn handle_convert(args: &ConvertArgs) -> Result<(), Box<dyn Error>> {
             .ok_or(ParseError::TooLarge)?;
         println!("{}x{}", width, height);
     } else if let Some(height) = args.height {
-        if height > 100_000_000 {
+        if height > MAX_VALUE {
             return Err(Box::new(ParseError::TooLarge));
         }
         let width = height
@@ -195,10 +207,63 @@ fn handle_convert(args: &ConvertArgs) -> Result<(), Box<dyn Error>> {
 }
 
 fn handle_info(args: &InfoArgs) -> Result<(), Box<dyn Error>> {
-    let (w, h) = parse_ratio(&args.ratio)?;
+    let (w, h) = parse_ratio(&args.ratio, args.tolerance)?;
     let decimal: f64 = w as f64 / h as f64;
     println!("Aspect Ratio: {}:{}", w, h);
     println!("Decimal: {:.6}", decimal);
+    if let Some(found) = standard::classify(w, h) {
+        let orientation: &str = if found.portrait { ", portrait" } else { "" };
+        if found.is_exact() {
+            println!(
+                "Standard: {} ({}{})",
+                found.ratio.name, found.ratio.description, orientation
+            );
+        } else {
+            println!(
+                "Nearest standard: {} ({}{}), {:+.2}% off",
+                found.ratio.name, found.ratio.description, orientation, found.deviation_percent
+            );
+        }
+    } else {
+        println!("Standard: none (non-standard ratio)");
+    }
+    Ok(())
+}
+
+fn handle_resolutions(args: &ResolutionsArgs) -> Result<(), Box<dyn Error>> {
+    let (w, h) = parse_ratio(&args.ratio, DEFAULT_TOLERANCE_PERCENT)?;
+    let min: u32 = args.min.unwrap_or(0);
+    let max: u32 = args.max.unwrap_or(resolutions::DEFAULT_MAX);
+    if min > max {
+        return Err(format!("--min ({}) is larger than --max ({})", min, max).into());
+    }
+    if max > MAX_VALUE {
+        return Err(Box::new(ParseError::TooLarge));
+    }
+    let mut found: usize = 0;
+    if let Some(step) = args.custom_step {
+        for res in resolutions::stepped(w, h, step, min, max) {
+            println!("{}x{}", res.width, res.height);
+            found += 1;
+        }
+    } else {
+        for res in resolutions::canonical(w, h, min, max) {
+            println!(
+                "{:<6} {}x{}",
+                res.label.unwrap_or_default(),
+                res.width,
+                res.height
+            );
+            found += 1;
+        }
+    }
+    if found == 0 {
+        return Err(format!(
+            "No resolutions between {} and {} pixels with edges up to {}",
+            min, max, MAX_VALUE
+        )
+        .into());
+    }
     Ok(())
 }
 
@@ -220,22 +285,34 @@ fn handle_completions(shell: &str) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn handle_calc(args: &CalcArgs) -> Result<(), Box<dyn Error>> {
-    if let Some(arg2) = &args.arg2 {
-        let w: u32 = args.arg1.parse().map_err(|_| ParseError::InvalidNumbers)?;
+/// Reduces a ratio given as one argument (`16:9`) or as width and height
+fn reduce_input(arg1: &str, arg2: Option<&str>) -> Result<(u32, u32), ParseError> {
+    let (w, h) = if let Some(arg2) = arg2 {
+        let w: u32 = arg1.parse().map_err(|_| ParseError::InvalidNumbers)?;
         let h: u32 = arg2.parse().map_err(|_| ParseError::InvalidNumbers)?;
-        if w == 0 || h == 0 {
-            return Err(Box::new(ParseError::NonPositiveNumbers));
-        }
-        if w > 100_000_000 || h > 100_000_000 {
-            return Err(Box::new(ParseError::TooLarge));
-        }
-        let (rw, rh) = aspect_ratio::get_aspect_ratio(w, h);
-        println!("{}:{}", rw, rh);
+        check_dimensions(w, h)?;
+        (w, h)
     } else {
-        let (w, h) = parse_ratio(&args.arg1)?;
-        let (rw, rh) = aspect_ratio::get_aspect_ratio(w, h);
+        parse_ratio(arg1, DEFAULT_TOLERANCE_PERCENT)?
+    };
+    Ok(aspect_ratio::get_aspect_ratio(w, h))
+}
+
+fn handle_calc(args: &CalcArgs) -> Result<(), Box<dyn Error>> {
+    let failed: usize = if args.stdin {
+        batch::run(io::stdin().lock(), &mut io::stdout().lock(), args.json)?
+    } else if let Some(path) = &args.file {
+        let file: File =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        batch::run(BufReader::new(file), &mut io::stdout().lock(), args.json)?
+    } else {
+        let arg1: &str = args.arg1.as_deref().unwrap_or_default();
+        let (rw, rh) = reduce_input(arg1, args.arg2.as_deref())?;
         println!("{}:{}", rw, rh);
+        0
+    };
+    if failed > 0 {
+        return Err(format!("{} line(s) could not be reduced", failed).into());
     }
     Ok(())
 }
@@ -247,6 +324,7 @@ fn main() {
         Some(Commands::Info(args)) => handle_info(args),
         Some(Commands::Completions { shell }) => handle_completions(shell),
         Some(Commands::Calc(args)) => handle_calc(args),
+        Some(Commands::Resolutions(args)) => handle_resolutions(args),
         None => {
             Cli::command()
                 .print_help()
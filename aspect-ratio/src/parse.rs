@@ -0,0 +1,191 @@
+//! Parsing of ratio arguments: integer pairs, decimals and percentages.
+
+use std::error::Error;
+use std::fmt;
+
+/// Largest width, height or approximation term accepted
+pub const MAX_VALUE: u32 = 100_000_000;
+
+/// Default allowed error, in percent, when approximating a decimal ratio
+pub const DEFAULT_TOLERANCE_PERCENT: f64 = 0.1;
+
+/// Continued fraction expansion stops after this many terms
+const MAX_TERMS: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    InvalidFormat,
+    InvalidNumbers,
+    NonPositiveNumbers,
+    TooLarge,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidFormat => write!(f, "Invalid input format"),
+            ParseError::InvalidNumbers => write!(f, "Invalid numbers provided"),
+            ParseError::NonPositiveNumbers => {
+                write!(f, "Width and height must be positive numbers")
+            }
+            ParseError::TooLarge => write!(f, "Numbers are too large"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a ratio in any of these forms:
+///
+/// - `1920x1080`, `16:9` or `16×9`: kept as given
+/// - `1.777` or `2.39:1`: approximated by a fraction within `tolerance_percent`
+/// - `56.25%`: the height as a percentage of the width, so `56.25%` is 16:9
+pub fn parse_ratio(input: &str, tolerance_percent: f64) -> Result<(u32, u32), ParseError> {
+    let input: &str = input.trim();
+    if let Some(percent) = input.strip_suffix('%') {
+        let percent: f64 = parse_decimal(percent)?;
+        return approximate(100.0 / percent, tolerance_percent);
+    }
+    let Some((w, h)) = input.split_once(['x', ':', '×']) else {
+        // Anything that isn't a lone decimal has the wrong shape entirely
+        let value: f64 = parse_decimal(input).map_err(|e| match e {
+            ParseError::InvalidNumbers => ParseError::InvalidFormat,
+            other => other,
+        })?;
+        return approximate(value, tolerance_percent);
+    };
+    let (w, h) = (w.trim(), h.trim());
+    if w.contains('.') || h.contains('.') {
+        return approximate(parse_decimal(w)? / parse_decimal(h)?, tolerance_percent);
+    }
+    let width: u32 = w.parse().map_err(|_| ParseError::InvalidNumbers)?;
+    let height: u32 = h.parse().map_err(|_| ParseError::InvalidNumbers)?;
+    check_dimensions(width, height)?;
+    Ok((width, height))
+}
+
+/// Rejects zero and oversized dimensions
+pub fn check_dimensions(width: u32, height: u32) -> Result<(), ParseError> {
+    if width == 0 || height == 0 {
+        return Err(ParseError::NonPositiveNumbers);
+    }
+    if width > MAX_VALUE || height > MAX_VALUE {
+        return Err(ParseError::TooLarge);
+    }
+    Ok(())
+}
+
+/// Parses a plain positive decimal such as `2.35`. Signs, exponents and
+/// `inf`/`nan`, which `f64::from_str` would accept, are rejected.
+fn parse_decimal(input: &str) -> Result<f64, ParseError> {
+    let input: &str = input.trim();
+    if input.is_empty() || !input.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return Err(ParseError::InvalidNumbers);
+    }
+    let value: f64 = input.parse().map_err(|_| ParseError::InvalidNumbers)?;
+    if value <= 0.0 {
+        return Err(ParseError::NonPositiveNumbers);
+    }
+    Ok(value)
+}
+
+/// Finds the first continued fraction convergent of `value` whose relative
+/// error is within `tolerance_percent`, or the closest one that still fits
+/// in [`MAX_VALUE`]
+pub fn approximate(value: f64, tolerance_percent: f64) -> Result<(u32, u32), ParseError> {
+    if !value.is_finite() || value <= 0.0 {
+        return Err(ParseError::NonPositiveNumbers);
+    }
+    let limit: u64 = MAX_VALUE as u64;
+    // Numerators and denominators of the two previous convergents
+    let (mut h_prev, mut h): (u64, u64) = (0, 1);
+    let (mut k_prev, mut k): (u64, u64) = (1, 0);
+    let mut best: Option<(u64, u64)> = None;
+    let mut x: f64 = value;
+    for _ in 0..MAX_TERMS {
+        let term: f64 = x.floor();
+        if term > limit as f64 {
+            break;
+        }
+        let (h_next, k_next): (u64, u64) = (term as u64 * h + h_prev, term as u64 * k + k_prev);
+        if h_next > limit || k_next > limit {
+            break;
+        }
+        (h_prev, h, k_prev, k) = (h, h_next, k, k_next);
+        if h > 0 {
+            best = Some((h, k));
+            let error: f64 = (h as f64 / k as f64 - value).abs() / value * 100.0;
+            if error <= tolerance_percent {
+                break;
+            }
+        }
+        let fraction: f64 = x - term;
+        if fraction <= f64::EPSILON {
+            break;
+        }
+        x = 1.0 / fraction;
+    }
+    best.map(|(h, k)| (h as u32, k as u32))
+        .ok_or(ParseError::TooLarge)
+}
+
+/// Clap value parser for `--tolerance`
+pub fn parse_tolerance(input: &str) -> Result<f64, String> {
+    let value: f64 = input
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", input))?;
+    if !value.is_finite() || value < 0.0 {
+        return Err("tolerance must be a non-negative percentage".to_string());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOL: f64 = DEFAULT_TOLERANCE_PERCENT;
+
+    #[test]
+    fn test_integer_ratios_are_kept() {
+        assert_eq!(parse_ratio("1920x1080", TOL), Ok((1920, 1080)));
+        assert_eq!(parse_ratio("16:9", TOL), Ok((16, 9)));
+        assert_eq!(parse_ratio(" 4 × 3 ", TOL), Ok((4, 3)));
+    }
+
+    #[test]
+    fn test_decimal_ratios_are_approximated() {
+        assert_eq!(parse_ratio("1.777", TOL), Ok((16, 9)));
+        assert_eq!(parse_ratio("1.85", TOL), Ok((37, 20)));
+        assert_eq!(parse_ratio("0.5625", TOL), Ok((9, 16)));
+        assert_eq!(parse_ratio("2.39:1", TOL), Ok((43, 18)));
+        assert_eq!(parse_ratio("2.39:1", 0.0), Ok((239, 100)));
+        assert_eq!(parse_ratio("3", TOL), Ok((3, 1)));
+    }
+
+    #[test]
+    fn test_tolerance_controls_precision() {
+        assert_eq!(parse_ratio("1.777", 2.0), Ok((7, 4)));
+        assert_eq!(parse_ratio("1.777", 0.0), Ok((1777, 1000)));
+    }
+
+    #[test]
+    fn test_percentages_are_height_over_width() {
+        assert_eq!(parse_ratio("56.25%", TOL), Ok((16, 9)));
+        assert_eq!(parse_ratio("75%", TOL), Ok((4, 3)));
+        assert_eq!(parse_ratio("100%", TOL), Ok((1, 1)));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(parse_ratio("16/9", TOL), Err(ParseError::InvalidFormat));
+        assert_eq!(parse_ratio("abc", TOL), Err(ParseError::InvalidFormat));
+        assert_eq!(parse_ratio("-1.5", TOL), Err(ParseError::InvalidFormat));
+        assert_eq!(parse_ratio("1e3", TOL), Err(ParseError::InvalidFormat));
+        assert_eq!(parse_ratio("0%", TOL), Err(ParseError::NonPositiveNumbers));
+        assert_eq!(parse_ratio("0:9", TOL), Err(ParseError::NonPositiveNumbers));
+        assert_eq!(parse_ratio("200000000x1", TOL), Err(ParseError::TooLarge));
+        assert!(parse_tolerance("-1").is_err());
+        assert_eq!(parse_tolerance("0.5"), Ok(0.5));
+    }
+}
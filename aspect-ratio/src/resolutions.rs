@@ -0,0 +1,144 @@
+//! Canonical and generated resolutions for an aspect ratio.
+
+use crate::parse::MAX_VALUE;
+
+/// Named resolution tiers, keyed by the length of the short edge
+pub const TIERS: &[(&str, u32)] = &[
+    ("720p", 720),
+    ("1080p", 1080),
+    ("1440p", 1440),
+    ("4K", 2160),
+    ("8K", 4320),
+];
+
+/// Largest short edge listed when no `--max` is given
+pub const DEFAULT_MAX: u32 = 4320;
+
+#[derive(Debug, PartialEq)]
+pub struct Resolution {
+    pub label: Option<&'static str>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Converts an edge length, rejecting anything above [`MAX_VALUE`]
+fn edge(length: u64) -> Option<u32> {
+    u32::try_from(length)
+        .ok()
+        .filter(|&length| length <= MAX_VALUE)
+}
+
+/// Scales `width:height` so that its short edge is `short`, rounding the
+/// long edge to the nearest pixel. Returns `None` when the long edge would
+/// exceed [`MAX_VALUE`].
+fn scale(width: u32, height: u32, short: u32) -> Option<Resolution> {
+    let (short_side, long_side): (u64, u64) = if height > width {
+        (width as u64, height as u64)
+    } else {
+        (height as u64, width as u64)
+    };
+    let long: u32 = edge((short as u64 * long_side + short_side / 2) / short_side)?;
+    let (width, height) = if height > width {
+        (short, long)
+    } else {
+        (long, short)
+    };
+    Some(Resolution {
+        label: None,
+        width,
+        height,
+    })
+}
+
+/// Lists the canonical tiers whose short edge lies within `min..=max`. The
+/// tiers apply to the height of landscape ratios and the width of portrait
+/// ones, so 9:16 at 1080p is 1080x1920. Tiers whose long edge would exceed
+/// [`MAX_VALUE`] are left out.
+pub fn canonical(width: u32, height: u32, min: u32, max: u32) -> Vec<Resolution> {
+    TIERS
+        .iter()
+        .filter(|(_, short)| (min..=max).contains(short))
+        .filter_map(|&(label, short)| {
+            Some(Resolution {
+                label: Some(label),
+                ..scale(width, height, short)?
+            })
+        })
+        .collect()
+}
+
+/// Yields every `step`-th integer multiple of the reduced `width:height`
+/// whose short edge lies within `min..=max`. Each result keeps the ratio
+/// exactly, unlike the rounded canonical tiers. Stops before the first
+/// multiple whose long edge would exceed [`MAX_VALUE`].
+pub fn stepped(
+    width: u32,
+    height: u32,
+    step: u32,
+    min: u32,
+    max: u32,
+) -> impl Iterator<Item = Resolution> {
+    let (width, height) = crate::aspect_ratio::get_aspect_ratio(width, height);
+    let short: u64 = width.min(height) as u64;
+    let step: u64 = step.max(1) as u64;
+    // First multiple of `step` whose short edge reaches `min`
+    let first: u64 = (min as u64).div_ceil(short * step).max(1) * step;
+    (first..)
+        .step_by(step as usize)
+        .take_while(move |k| k * short <= max as u64)
+        .map_while(move |k| {
+            Some(Resolution {
+                label: None,
+                width: edge(k * width as u64)?,
+                height: edge(k * height as u64)?,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_tiers_for_16_9() {
+        let found: Vec<Resolution> = canonical(16, 9, 0, DEFAULT_MAX);
+        let sizes: Vec<(u32, u32)> = found.iter().map(|r| (r.width, r.height)).collect();
+        assert_eq!(
+            sizes,
+            [
+                (1280, 720),
+                (1920, 1080),
+                (2560, 1440),
+                (3840, 2160),
+                (7680, 4320)
+            ]
+        );
+        assert_eq!(found[3].label, Some("4K"));
+    }
+
+    #[test]
+    fn test_canonical_bounds_and_portrait() {
+        let found: Vec<Resolution> = canonical(9, 16, 1080, 1440);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].width, found[0].height), (1080, 1920));
+        assert_eq!((found[1].width, found[1].height), (1440, 2560));
+    }
+
+    #[test]
+    fn test_stepped_multiples_within_bounds() {
+        let found: Vec<(u32, u32)> = stepped(1920, 1080, 40, 1000, 1440)
+            .map(|r| (r.width, r.height))
+            .collect();
+        assert_eq!(found, [(1920, 1080), (2560, 1440)]);
+    }
+
+    #[test]
+    fn test_sizes_beyond_max_value_are_skipped() {
+        assert!(canonical(MAX_VALUE, 1, 0, DEFAULT_MAX).is_empty());
+        let found: Vec<Resolution> = canonical(1, 100_000, 0, DEFAULT_MAX);
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].width, found[0].height), (720, 72_000_000));
+        assert_eq!(stepped(MAX_VALUE, 1, 1, 0, DEFAULT_MAX).count(), 1);
+        assert_eq!(stepped(100_000, 1, 1, 0, DEFAULT_MAX).count(), 1000);
+    }
+}
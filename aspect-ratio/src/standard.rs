@@ -0,0 +1,182 @@
+//! Table of well-known aspect ratios and nearest-match lookup.
+
+/// A named aspect ratio, stored as width divided by height (landscape)
+#[derive(Debug, PartialEq)]
+pub struct StandardRatio {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub value: f64,
+}
+
+pub const GOLDEN_RATIO: f64 = 1.618_033_988_749_895;
+
+/// Ratios within this relative deviation (in percent) count as exact, so
+/// rounded resolutions such as 1366x768 still match 16:9
+pub const EXACT_TOLERANCE_PERCENT: f64 = 0.5;
+
+/// Ratios further than this (in percent) from every standard one are
+/// reported as non-standard rather than given a nearest match
+pub const NEAREST_TOLERANCE_PERCENT: f64 = 10.0;
+
+pub const STANDARD_RATIOS: &[StandardRatio] = &[
+    StandardRatio {
+        name: "1:1",
+        description: "square",
+        value: 1.0,
+    },
+    StandardRatio {
+        name: "5:4",
+        description: "SXGA monitor",
+        value: 1.25,
+    },
+    StandardRatio {
+        name: "4:3",
+        description: "standard TV and monitor",
+        value: 4.0 / 3.0,
+    },
+    StandardRatio {
+        name: "3:2",
+        description: "35mm photo",
+        value: 1.5,
+    },
+    StandardRatio {
+        name: "16:10",
+        description: "widescreen monitor",
+        value: 1.6,
+    },
+    StandardRatio {
+        name: "φ:1",
+        description: "golden ratio",
+        value: GOLDEN_RATIO,
+    },
+    StandardRatio {
+        name: "5:3",
+        description: "super 16mm film",
+        value: 5.0 / 3.0,
+    },
+    StandardRatio {
+        name: "16:9",
+        description: "HD video",
+        value: 16.0 / 9.0,
+    },
+    StandardRatio {
+        name: "1.85:1",
+        description: "flat widescreen film",
+        value: 1.85,
+    },
+    StandardRatio {
+        name: "2:1",
+        description: "univisium",
+        value: 2.0,
+    },
+    StandardRatio {
+        name: "21:9",
+        description: "ultrawide",
+        value: 21.0 / 9.0,
+    },
+    StandardRatio {
+        name: "64:27",
+        description: "ultrawide monitor",
+        value: 64.0 / 27.0,
+    },
+    StandardRatio {
+        name: "2.39:1",
+        description: "anamorphic scope film",
+        value: 2.39,
+    },
+    StandardRatio {
+        name: "32:9",
+        description: "super ultrawide",
+        value: 32.0 / 9.0,
+    },
+];
+
+/// The standard ratio closest to an input
+#[derive(Debug, PartialEq)]
+pub struct Classification {
+    pub ratio: &'static StandardRatio,
+    /// Signed deviation of the input from `ratio`, in percent
+    pub deviation_percent: f64,
+    /// Whether the input is taller than it is wide
+    pub portrait: bool,
+}
+
+impl Classification {
+    pub fn is_exact(&self) -> bool {
+        self.deviation_percent.abs() <= EXACT_TOLERANCE_PERCENT
+    }
+}
+
+/// Finds the nearest standard ratio, comparing portrait inputs against the
+/// landscape table by swapping width and height. Returns `None` when none is
+/// within [`NEAREST_TOLERANCE_PERCENT`].
+pub fn classify(width: u32, height: u32) -> Option<Classification> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let portrait: bool = height > width;
+    let value: f64 = if portrait {
+        height as f64 / width as f64
+    } else {
+        width as f64 / height as f64
+    };
+    STANDARD_RATIOS
+        .iter()
+        .map(|ratio| Classification {
+            ratio,
+            deviation_percent: (value - ratio.value) / ratio.value * 100.0,
+            portrait,
+        })
+        .min_by(|a, b| {
+            a.deviation_percent
+                .abs()
+                .total_cmp(&b.deviation_percent.abs())
+        })
+        .filter(|found| found.deviation_percent.abs() <= NEAREST_TOLERANCE_PERCENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recognizes_common_resolutions() {
+        for (width, height, name) in [
+            (1920, 1080, "16:9"),
+            (1366, 768, "16:9"),
+            (1024, 768, "4:3"),
+            (1998, 1080, "1.85:1"),
+            (2560, 1080, "64:27"),
+            (1920, 1200, "16:10"),
+            (500, 309, "φ:1"),
+        ] {
+            let found = classify(width, height).unwrap();
+            assert_eq!(found.ratio.name, name, "{}x{}", width, height);
+            assert!(found.is_exact(), "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn test_reports_nearest_ratio_with_deviation() {
+        let found = classify(1900, 1000).unwrap();
+        assert_eq!(found.ratio.name, "1.85:1");
+        assert!(!found.is_exact());
+        assert!((found.deviation_percent - 2.7027).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_portrait_matches_landscape_table() {
+        let found = classify(1080, 1920).unwrap();
+        assert_eq!(found.ratio.name, "16:9");
+        assert!(found.portrait);
+        assert!(classify(0, 1080).is_none());
+    }
+
+    #[test]
+    fn test_distant_ratios_are_non_standard() {
+        assert!(classify(100, 1).is_none());
+        assert!(classify(1, 10_000_000).is_none());
+        assert!(classify(40, 9).is_none());
+        assert_eq!(classify(34, 9).unwrap().ratio.name, "32:9");
+    }
+}
@@ -0,0 +1,260 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use rayon::prelude::*;
+
+/// Comment syntax used to classify source lines
+#[derive(Debug, Clone, Default)]
+struct CommentSyntax {
+    line: Vec<String>,
+    block: Option<(&'static str, &'static str)>,
+}
+
+const C_STYLE: &[&str] = &[
+    "c", "h", "cc", "cpp", "cxx", "hpp", "cs", "go", "java", "js", "jsx", "kt", "kts", "rs",
+    "scala", "swift", "ts", "tsx", "zig",
+];
+const HASH_STYLE: &[&str] = &[
+    "bash", "fish", "nix", "pl", "py", "r", "rb", "sh", "toml", "yaml", "yml", "zsh",
+];
+const DASH_STYLE: &[&str] = &["hs", "lua", "sql"];
+const MARKUP_STYLE: &[&str] = &["html", "md", "svg", "xml"];
+
+impl CommentSyntax {
+    /// Picks the comment syntax from the file extension
+    fn detect(path: &str) -> Self {
+        let extension: String = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        let ext: &str = extension.as_str();
+        let (line, block): (&[&str], Option<(&'static str, &'static str)>) =
+            if C_STYLE.contains(&ext) {
+                (&["//"], Some(("/*", "*/")))
+            } else if HASH_STYLE.contains(&ext) {
+                (&["#"], None)
+            } else if DASH_STYLE.contains(&ext) {
+                (&["--"], None)
+            } else if MARKUP_STYLE.contains(&ext) {
+                (&[], Some(("<!--", "-->")))
+            } else if ext == "css" {
+                (&[], Some(("/*", "*/")))
+            } else {
+                (&[], None)
+            };
+        CommentSyntax {
+            line: line.iter().map(|p| p.to_string()).collect(),
+            block,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct CodeResult {
+    total: usize,
+    blank: usize,
+    comment: usize,
+    code: usize,
+}
+
+impl CodeResult {
+    fn add(&mut self, other: &CodeResult) {
+        self.total += other.total;
+        self.blank += other.blank;
+        self.comment += other.comment;
+        self.code += other.code;
+    }
+
+    fn row(&self, label: &str) -> String {
+        format!(
+            "{:>8}  {:>8}  {:>8}  {:>8}  {}",
+            self.total, self.blank, self.comment, self.code, label
+        )
+    }
+}
+
+/// Classifies each line as blank, comment or code. A line with any code
+/// outside comments counts as code, so `x = 1 /* start` is code and opens a
+/// block comment that covers the following lines.
+fn count_code<R: BufRead>(mut reader: R, syntax: &CommentSyntax) -> io::Result<CodeResult> {
+    let mut res: CodeResult = CodeResult::default();
+    let mut in_block: bool = false;
+    let mut buf: Vec<u8> = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        let trimmed: &str = line.trim();
+        res.total += 1;
+        if trimmed.is_empty() && !in_block {
+            res.blank += 1;
+            continue;
+        }
+        let (has_code, has_comment) = scan_line(trimmed, syntax, &mut in_block);
+        if has_code {
+            res.code += 1;
+        } else if has_comment || trimmed.is_empty() {
+            res.comment += 1;
+        } else {
+            res.blank += 1;
+        }
+    }
+    Ok(res)
+}
+
+/// Walks one line, tracking block comment state across it. Returns whether
+/// the line has any code and whether it has any comment.
+fn scan_line(line: &str, syntax: &CommentSyntax, in_block: &mut bool) -> (bool, bool) {
+    let mut has_code: bool = false;
+    let mut has_comment: bool = false;
+    let mut rest: &str = line;
+    loop {
+        if *in_block {
+            has_comment = true;
+            let Some((_, end)) = syntax.block else {
+                *in_block = false;
+                continue;
+            };
+            match rest.find(end) {
+                Some(i) => {
+                    rest = &rest[i + end.len()..];
+                    *in_block = false;
+                }
+                None => break,
+            }
+            continue;
+        }
+
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        if syntax.line.iter().any(|p| rest.starts_with(p.as_str())) {
+            has_comment = true;
+            break;
+        }
+        if let Some(after) = syntax.block.and_then(|(start, _)| rest.strip_prefix(start)) {
+            rest = after;
+            *in_block = true;
+            continue;
+        }
+
+        // Code up to the next comment marker, if any
+        has_code = true;
+        let next: Option<usize> = syntax
+            .line
+            .iter()
+            .map(String::as_str)
+            .chain(syntax.block.map(|(start, _)| start))
+            .filter_map(|marker| rest.find(marker))
+            .min();
+        match next {
+            Some(i) => rest = &rest[i..],
+            None => break,
+        }
+    }
+    (has_code, has_comment)
+}
+
+fn count_path(file_path: &str, comment_prefixes: &[String]) -> io::Result<CodeResult> {
+    let syntax: CommentSyntax = if comment_prefixes.is_empty() {
+        CommentSyntax::detect(file_path)
+    } else {
+        CommentSyntax {
+            line: comment_prefixes.to_vec(),
+            block: None,
+        }
+    };
+    let reader: Box<dyn BufRead> = if file_path == "-" {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        Box::new(BufReader::new(File::open(file_path)?))
+    };
+    count_code(reader, &syntax)
+}
+
+/// Reports total, blank, comment and code lines per file
+pub fn handle_code(
+    files: &[String],
+    comment_prefixes: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    if files.iter().any(|f: &String| f.is_empty()) {
+        return Err("Empty file name provided".into());
+    }
+    let inputs: Vec<String> = if files.is_empty() {
+        vec!["-".to_string()]
+    } else {
+        files.to_vec()
+    };
+    if files.is_empty() {
+        eprintln!(
+            "Paste your code, then press Ctrl+D (on Mac/Linux) or Ctrl+Z (on Windows) to finish:"
+        );
+    }
+    // Files are counted in parallel but reported in the order given
+    let results: Vec<io::Result<CodeResult>> = inputs
+        .par_iter()
+        .map(|file_path: &String| count_path(file_path, comment_prefixes))
+        .collect();
+    println!("   total     blank   comment      code  file");
+    let mut total: CodeResult = CodeResult::default();
+    for (file_path, res) in inputs.iter().zip(results) {
+        let res: CodeResult = res?;
+        total.add(&res);
+        println!("{}", res.row(file_path));
+    }
+    if inputs.len() > 1 {
+        println!("{}", total.row("total"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(input: &[u8], path: &str) -> CodeResult {
+        count_code(input, &CommentSyntax::detect(path)).unwrap()
+    }
+
+    fn counts(res: CodeResult) -> (usize, usize, usize, usize) {
+        (res.total, res.blank, res.comment, res.code)
+    }
+
+    #[test]
+    fn test_line_and_block_comments() {
+        let input: &[u8] =
+            b"// header\n\nfn main() {}\n/* one\n\n   two */\nlet x = 1; // trailing\n";
+        assert_eq!(counts(count(input, "a.rs")), (7, 1, 4, 2));
+    }
+
+    #[test]
+    fn test_block_opened_and_closed_mid_line() {
+        let input: &[u8] =
+            b"x = 1 /* start\n comment\n end */\n/* a */ y = 2\n/* a */ /* b */\nz */ w\n";
+        // The last line is code: `z */ w` is outside any block
+        assert_eq!(counts(count(input, "a.c")), (6, 0, 3, 3));
+    }
+
+    #[test]
+    fn test_invalid_utf8_and_missing_newline_are_counted() {
+        let input: &[u8] = b"# \xff comment\nvalue = \xfe\nlast";
+        assert_eq!(counts(count(input, "a.py")), (3, 0, 1, 2));
+    }
+
+    #[test]
+    fn test_read_errors_are_returned() {
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("read failed"))
+            }
+        }
+        let syntax: CommentSyntax = CommentSyntax::detect("a.rs");
+        assert!(count_code(BufReader::new(Failing), &syntax).is_err());
+    }
+}
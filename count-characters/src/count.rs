@@ -0,0 +1,206 @@
+use std::io::{self, ErrorKind, Read};
+
+/// Bytes read from the input per call
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct WcResult {
+    pub lines: usize,
+    pub words: usize,
+    pub bytes: usize,
+    pub chars: usize,
+    pub longest_line: usize,
+}
+
+impl WcResult {
+    pub fn add(&mut self, other: &WcResult) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.bytes += other.bytes;
+        self.chars += other.chars;
+        self.longest_line = self.longest_line.max(other.longest_line);
+    }
+}
+
+/// Running state carried across chunks
+#[derive(Default)]
+struct Counter {
+    res: WcResult,
+    in_word: bool,
+    line_bytes: usize,
+    line_chars: usize,
+    longest_bytes: usize,
+    longest_chars: usize,
+    prev_cr: bool,
+}
+
+impl Counter {
+    fn char(&mut self, c: char) {
+        self.res.chars += 1;
+        if c == '\n' {
+            self.res.lines += 1;
+            self.end_line();
+        } else {
+            self.line_bytes += c.len_utf8();
+            self.line_chars += 1;
+        }
+        self.prev_cr = c == '\r';
+        if c.is_whitespace() {
+            self.in_word = false;
+        } else if !self.in_word {
+            self.res.words += 1;
+            self.in_word = true;
+        }
+    }
+
+    /// Bytes that aren't valid UTF-8 count as bytes but not as characters,
+    /// and neither start nor end a word, the same as GNU wc
+    fn invalid(&mut self, len: usize) {
+        self.line_bytes += len;
+        self.prev_cr = false;
+    }
+
+    /// Records the finished line's length, leaving out the `\r` of a `\r\n`
+    /// ending
+    fn end_line(&mut self) {
+        let cr: usize = usize::from(self.prev_cr);
+        self.longest_bytes = self.longest_bytes.max(self.line_bytes - cr);
+        self.longest_chars = self.longest_chars.max(self.line_chars - cr);
+        self.line_bytes = 0;
+        self.line_chars = 0;
+    }
+}
+
+/// Counts lines, words, bytes and characters from raw bytes, like `wc`.
+/// Lines are `\n` bytes, so a last line without a trailing newline isn't
+/// counted; characters are decoded as UTF-8, carrying sequences that are
+/// split across reads over to the next chunk.
+pub fn count_stats<R: Read>(mut reader: R, use_chars_for_longest: bool) -> io::Result<WcResult> {
+    let mut counter: Counter = Counter::default();
+    let mut buf: Vec<u8> = vec![0; CHUNK_SIZE];
+    // Start of an incomplete UTF-8 sequence left over from the last chunk
+    let mut pending: Vec<u8> = Vec::with_capacity(4);
+    loop {
+        let read: usize = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        counter.res.bytes += read;
+        let joined: Vec<u8>;
+        let mut rest: &[u8] = if pending.is_empty() {
+            &buf[..read]
+        } else {
+            joined = [std::mem::take(&mut pending).as_slice(), &buf[..read]].concat();
+            &joined
+        };
+        while !rest.is_empty() {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    text.chars().for_each(|c| counter.char(c));
+                    break;
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    std::str::from_utf8(valid)
+                        .unwrap_or_default()
+                        .chars()
+                        .for_each(|c| counter.char(c));
+                    match e.error_len() {
+                        Some(len) => {
+                            counter.invalid(len);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            pending.extend_from_slice(after);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    // A sequence still incomplete at the end of input is invalid
+    counter.invalid(pending.len());
+    counter.end_line();
+
+    counter.res.longest_line = if use_chars_for_longest {
+        counter.longest_chars
+    } else {
+        counter.longest_bytes
+    };
+    Ok(counter.res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader handing out one byte per call, to split every UTF-8 sequence
+    /// across reads
+    struct OneByte<'a>(&'a [u8]);
+
+    impl Read for OneByte<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self.0.split_first() {
+                Some((&b, rest)) if !buf.is_empty() => {
+                    buf[0] = b;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    /// `(lines, words, chars, bytes)`, the order `wc -l -w -m -c` prints
+    type Counts = (usize, usize, usize, usize);
+
+    /// Expected counts from GNU wc 9.1 with `LC_ALL=C.UTF-8`
+    const GNU_WC: &[(&[u8], Counts)] = &[
+        (b"", (0, 0, 0, 0)),
+        (b"no newline", (0, 2, 10, 10)),
+        (b"a\r\nb\r\n", (2, 2, 6, 6)),
+        (b"  two  words\t\n\n", (2, 2, 15, 15)),
+        (b"\xff", (0, 0, 0, 1)),
+        (b"a\xc3", (0, 1, 1, 2)),
+        (b"a\xc2\xa0b", (0, 2, 3, 4)),
+        (
+            "日本語 テキスト\n\u{2003}em space\n".as_bytes(),
+            (2, 4, 19, 35),
+        ),
+        (
+            b"h\xc3\xa9llo w\xc3\xb6rld\r\nfoo\xffbar \xff baz\nno newline",
+            (2, 6, 35, 39),
+        ),
+    ];
+
+    #[test]
+    fn test_matches_gnu_wc() {
+        for (input, expected) in GNU_WC {
+            for res in [
+                count_stats(*input, false).unwrap(),
+                count_stats(OneByte(input), false).unwrap(),
+            ] {
+                assert_eq!(
+                    (res.lines, res.words, res.chars, res.bytes),
+                    *expected,
+                    "{:?}",
+                    String::from_utf8_lossy(input)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_longest_line_ignores_line_endings() {
+        let input: &[u8] = "abc\r\nh\u{e9}llo\nxy".as_bytes();
+        assert_eq!(count_stats(input, false).unwrap().longest_line, 6);
+        assert_eq!(count_stats(input, true).unwrap().longest_line, 5);
+        assert_eq!(
+            count_stats(&b"abcd\r\n"[..], false).unwrap().longest_line,
+            4
+        );
+    }
+}
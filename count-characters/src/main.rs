@@ -1,6 +1,12 @@
+mod code;
+mod count;
+
 use clap::{ArgGroup, Parser};
+use rayon::prelude::*;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, Read};
+
+use count::{count_stats, WcResult};
 
 /// Simple program to count characters in a file or from standard input
 #[derive(Parser, Debug)]
@@ -30,64 +36,27 @@ struct Args {
     /// The number of words in each input file
     #[arg(short = 'w', long = "words")]
     words: bool,
-}
 
-#[derive(Default, Debug, Clone, Copy)]
-struct WcResult {
-    lines: usize,
-    words: usize,
-    bytes: usize,
-    chars: usize,
-    longest_line: usize,
-}
+    /// Report total, blank, comment and code lines for source files
+    #[arg(long = "code", conflicts_with = "count_opts")]
+    code: bool,
 
-impl WcResult {
-    fn add(&mut self, other: &WcResult) {
-        self.lines += other.lines;
-        self.words += other.words;
-        self.bytes += other.bytes;
-        self.chars += other.chars;
-        self.longest_line = self.longest_line.max(other.longest_line);
-    }
+    /// Line comment prefix for --code, overriding detection from the file extension (repeatable)
+    #[arg(long = "comment-prefix", value_name = "PREFIX", requires = "code")]
+    comment_prefix: Vec<String>,
+
+    /// Number of files to count at once (defaults to the number of CPUs)
+    #[arg(long = "threads", value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
 }
 
-fn count_stats<R: BufRead>(
-    reader: R,
-    count_bytes: bool,
-    count_lines: bool,
-    count_chars: bool,
-    count_words: bool,
-    count_longest_line: bool,
-    use_chars_for_longest: bool,
-) -> WcResult {
-    let mut res: WcResult = WcResult::default();
-    for line in reader.lines() {
-        let line: String = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
-        if count_lines || count_longest_line {
-            res.lines += 1;
-        }
-        if count_words {
-            res.words += line.split_whitespace().count();
-        }
-        if count_bytes {
-            res.bytes += line.len() + 1;
-        }
-        if count_chars || use_chars_for_longest {
-            res.chars += line.chars().count() + 1;
-        }
-        if count_longest_line {
-            let len = if use_chars_for_longest {
-                line.chars().count()
-            } else {
-                line.len()
-            };
-            res.longest_line = res.longest_line.max(len);
-        }
-    }
-    res
+fn count_path(file_path: &str, use_chars_for_longest: bool) -> io::Result<WcResult> {
+    let reader: Box<dyn Read> = if file_path == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(File::open(file_path)?)
+    };
+    count_stats(reader, use_chars_for_longest)
 }
 
 fn handle_wc(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
@@ -138,25 +107,18 @@ fn handle_wc(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
     if !headers.is_empty() {
         println!("{}", headers.join("  "));
     }
-    for (i, file_path) in files.iter().enumerate() {
-        let reader: Box<dyn BufRead> = if file_path == "-" {
-            if i == 0 && args.files.is_empty() {
-                eprintln!("Paste your text, then press Ctrl+D (on Mac/Linux) or Ctrl+Z (on Windows) to finish:");
-            }
-            Box::new(BufReader::new(io::stdin()))
-        } else {
-            let file: File = File::open(file_path)?;
-            Box::new(BufReader::new(file))
-        };
-        let res = count_stats(
-            reader,
-            count_bytes,
-            count_lines,
-            count_chars,
-            count_words,
-            count_longest_line,
-            use_chars_for_longest,
+    if args.files.is_empty() {
+        eprintln!(
+            "Paste your text, then press Ctrl+D (on Mac/Linux) or Ctrl+Z (on Windows) to finish:"
         );
+    }
+    // Files are counted in parallel but reported in the order given
+    let results: Vec<io::Result<WcResult>> = files
+        .par_iter()
+        .map(|file_path: &String| count_path(file_path, use_chars_for_longest))
+        .collect();
+    for (file_path, res) in files.iter().zip(results) {
+        let res: WcResult = res?;
         total.add(&res);
         let mut output: Vec<String> = vec![];
         if count_lines {
@@ -204,5 +166,13 @@ fn handle_wc(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Args = Args::parse();
+    if let Some(threads) = args.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(usize::from(threads))
+            .build_global()?;
+    }
+    if args.code {
+        return code::handle_code(&args.files, &args.comment_prefix);
+    }
     handle_wc(&args)
 }
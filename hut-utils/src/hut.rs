@@ -1,7 +1,12 @@
-use crate::utils::{AppError, Colorize, HUT_COMMAND, PASTE_COMMAND, execute_hut_command};
-use std::fs::File;
-use std::io::{BufReader, Read, Write};
+use crate::utils::{
+    AppError, Colorize, HUT_COMMAND, PAGES_COMMAND, PASTE_COMMAND, TAR_COMMAND, execute_hut_command,
+};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+use which::which;
 
 pub const DEFAULT_VISIBILITY: &str = "unlisted";
 
@@ -129,3 +134,116 @@ pub fn show_paste(paste_id: &str) -> Result<String, AppError> {
     let output: String = execute_hut_command(&[PASTE_COMMAND, "show", paste_id])?;
     Ok(output)
 }
+
+pub const DEFAULT_PROTOCOL: &str = "https";
+
+#[derive(Clone, Debug, clap::ValueEnum)]
+pub enum Protocol {
+    Https,
+    Gemini,
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Protocol::Https => "HTTPS",
+            Protocol::Gemini => "GEMINI",
+        }
+    }
+}
+
+pub fn create_site_archive(directory: &Path) -> Result<PathBuf, AppError> {
+    if which(TAR_COMMAND).is_err() {
+        return Err(AppError::ValidationError(
+            "tar is not installed or not in PATH".to_string(),
+        ));
+    }
+
+    if !directory.is_dir() {
+        return Err(AppError::ValidationError(format!(
+            "'{}' is not a directory",
+            directory.display()
+        )));
+    }
+
+    let (archive, file): (PathBuf, File) = create_archive_file()?;
+
+    // tar writes to stdout so it never opens the archive path itself
+    let output: Output = Command::new(TAR_COMMAND)
+        .arg("-czf")
+        .arg("-")
+        .arg("-C")
+        .arg(directory)
+        .arg(".")
+        .stdout(Stdio::from(file))
+        .stderr(Stdio::piped())
+        .output()?;
+
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&archive);
+        return Err(AppError::CommandError(format!(
+            "Failed to create archive of directory '{}': {}",
+            directory.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(archive)
+}
+
+/// Creates a new, private archive file in the temp directory. The file is
+/// opened with `create_new` so an existing file or planted symlink is never
+/// reused.
+fn create_archive_file() -> Result<(PathBuf, File), AppError> {
+    let nanos: u32 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+
+    for attempt in 0..100 {
+        let archive: PathBuf = std::env::temp_dir().join(format!(
+            "hut-utils-pages-{}-{}-{}.tar.gz",
+            std::process::id(),
+            nanos,
+            attempt
+        ));
+
+        let mut options: OpenOptions = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        match options.open(&archive) {
+            Ok(file) => return Ok((archive, file)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(AppError::CommandError(
+        "Failed to create a temporary archive file".to_string(),
+    ))
+}
+
+pub fn publish_site(archive: &Path, domain: &str, protocol: Protocol) -> Result<(), AppError> {
+    let archive_path: String = archive.to_string_lossy().into_owned();
+
+    execute_hut_command(&[
+        PAGES_COMMAND,
+        "publish",
+        "--domain",
+        domain,
+        "--protocol",
+        protocol.as_str(),
+        &archive_path,
+    ])?;
+
+    println!(
+        "{} Successfully published site to {}://{}",
+        "[SUCCESS]".green().bold(),
+        protocol.as_str().to_lowercase(),
+        domain.cyan()
+    );
+
+    Ok(())
+}
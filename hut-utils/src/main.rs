@@ -1,9 +1,13 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use pages::{PagesCommands, handle_pages_command};
 use paste::{PasteCommands, handle_paste_command};
+use setup::{DEFAULT_INSTANCE, run_setup};
 use utils::validate_environment;
 
 mod hut;
+mod pages;
 mod paste;
+mod setup;
 mod utils;
 
 #[derive(Parser)]
@@ -20,6 +24,19 @@ enum Commands {
         #[command(subcommand)]
         action: PasteCommands,
     },
+
+    /// Pages related commands
+    Pages {
+        #[command(subcommand)]
+        action: PagesCommands,
+    },
+
+    /// Set up a sourcehut access token for hut and install shell completions
+    Setup {
+        /// Sourcehut instance to authenticate against
+        #[arg(short = 'i', long, default_value = DEFAULT_INSTANCE)]
+        instance: String,
+    },
 }
 
 fn main() {
@@ -28,5 +45,7 @@ fn main() {
 
     match cli.command {
         Commands::Paste { action } => handle_paste_command(action).unwrap(),
+        Commands::Pages { action } => handle_pages_command(action).unwrap(),
+        Commands::Setup { instance } => run_setup(&instance, Cli::command()).unwrap(),
     }
 }
@@ -0,0 +1,52 @@
+use crate::hut::{DEFAULT_PROTOCOL, Protocol, create_site_archive, publish_site};
+use crate::utils::{AppError, Colorize};
+use clap::Subcommand;
+use std::path::PathBuf;
+
+/// Pages related commands
+#[derive(Subcommand)]
+pub enum PagesCommands {
+    /// Publish a local directory as a static site on pages.sr.ht
+    Publish {
+        /// Directory containing the site to publish
+        #[arg(short = 's', long)]
+        source_dir: PathBuf,
+
+        /// Domain to publish the site to (e.g. username.srht.site)
+        #[arg(short = 'd', long)]
+        domain: String,
+
+        /// Protocol to serve the site over: Https, Gemini
+        #[arg(short = 'p', long, default_value = DEFAULT_PROTOCOL, value_enum)]
+        protocol: Protocol,
+    },
+}
+
+pub fn handle_pages_command(action: PagesCommands) -> Result<(), AppError> {
+    match action {
+        PagesCommands::Publish {
+            source_dir,
+            domain,
+            protocol,
+        } => {
+            println!(
+                "{} Packing {} into a tarball...",
+                "[INFO]".blue().bold(),
+                source_dir.display().to_string().cyan()
+            );
+            let archive: PathBuf = create_site_archive(&source_dir)?;
+
+            println!(
+                "{} Publishing site to {}...",
+                "[INFO]".blue().bold(),
+                domain.cyan()
+            );
+            let result: Result<(), AppError> = publish_site(&archive, &domain, protocol);
+
+            // Always clean up the temporary tarball, even if publishing failed
+            let _ = std::fs::remove_file(&archive);
+
+            result
+        }
+    }
+}
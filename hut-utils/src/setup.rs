@@ -0,0 +1,218 @@
+use crate::utils::{AppError, Colorize, HUT_COMMAND};
+use clap_complete::{Shell, generate};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+pub const DEFAULT_INSTANCE: &str = "sr.ht";
+const BIN_NAME: &str = "hut-utils";
+
+/// OAuth 2.0 grants used by the paste and pages commands
+const REQUIRED_GRANTS: &[&str] = &[
+    "meta.sr.ht/PROFILE:RO",
+    "paste.sr.ht/PASTES:RW",
+    "pages.sr.ht/PAGES:RW",
+];
+
+/// Walks through creating a personal access token, verifies it against
+/// meta.sr.ht and writes the hut config, then offers to install completions
+pub fn run_setup(instance: &str, mut command: clap::Command) -> Result<(), AppError> {
+    let config_path: PathBuf = hut_config_path()?;
+
+    if config_path.exists()
+        && !confirm(&format!(
+            "{} already exists. Replace it with a new token?",
+            config_path.display()
+        ))?
+    {
+        println!("{} Keeping the existing config", "[INFO]".blue().bold());
+    } else {
+        configure_token(instance, &config_path)?;
+    }
+
+    if confirm("Install shell completions for hut-utils?")? {
+        install_completions(&mut command)?;
+    }
+
+    Ok(())
+}
+
+fn configure_token(instance: &str, config_path: &Path) -> Result<(), AppError> {
+    println!(
+        "{} Create a personal access token at: {}",
+        "[INFO]".blue().bold(),
+        format!("https://meta.{}/oauth2/personal-token", instance).cyan()
+    );
+    println!(
+        "{} Limit it to these grants (or leave the list empty for full access):",
+        "[INFO]".blue().bold()
+    );
+    for grant in REQUIRED_GRANTS {
+        println!("  {}", grant.cyan());
+    }
+
+    let token: String = prompt_secret("Paste the token: ")?;
+    if token.is_empty() || token.contains(['"', '\\']) {
+        return Err(AppError::ValidationError(
+            "The token is empty or contains invalid characters".to_string(),
+        ));
+    }
+
+    // Verify with a candidate config first so a bad token never replaces a
+    // working config
+    let config_dir: &Path = config_path
+        .parent()
+        .ok_or_else(|| AppError::ValidationError("Invalid hut config path".to_string()))?;
+    std::fs::create_dir_all(config_dir)?;
+    let candidate: PathBuf = config_path.with_extension("new");
+    let _ = std::fs::remove_file(&candidate);
+    let mut file: File = create_private_file(&candidate)?;
+    writeln!(
+        file,
+        "instance \"{}\" {{\n\taccess-token \"{}\"\n}}",
+        instance, token
+    )?;
+    drop(file);
+
+    println!(
+        "{} Verifying the token against {}...",
+        "[INFO]".blue().bold(),
+        format!("meta.{}", instance).cyan()
+    );
+    let output: Output = Command::new(HUT_COMMAND)
+        .arg("--config")
+        .arg(&candidate)
+        .args(["meta", "show"])
+        .stderr(Stdio::piped())
+        .output()?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&candidate);
+        return Err(AppError::CommandError(format!(
+            "meta.{} rejected the token: {}",
+            instance,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    std::fs::rename(&candidate, config_path)?;
+    let profile: String = String::from_utf8_lossy(&output.stdout).into_owned();
+    println!(
+        "{} Authenticated as {}; wrote {}",
+        "[SUCCESS]".green().bold(),
+        profile.lines().next().unwrap_or_default().trim().cyan(),
+        config_path.display()
+    );
+
+    Ok(())
+}
+
+/// Location hut reads its config from
+fn hut_config_path() -> Result<PathBuf, AppError> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("hut").join("config"))
+        .ok_or_else(|| {
+            AppError::ValidationError("Could not determine the config directory".to_string())
+        })
+}
+
+fn install_completions(command: &mut clap::Command) -> Result<(), AppError> {
+    let shell: Shell = Shell::from_env().ok_or_else(|| {
+        AppError::ValidationError("Could not detect the shell from $SHELL".to_string())
+    })?;
+    let home: PathBuf = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| AppError::ValidationError("HOME is not set".to_string()))?;
+    let data_dir: PathBuf = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local").join("share"));
+    let config_dir: PathBuf = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+
+    let path: PathBuf = match shell {
+        Shell::Bash => data_dir
+            .join("bash-completion")
+            .join("completions")
+            .join(BIN_NAME),
+        Shell::Zsh => home.join(".zfunc").join(format!("_{}", BIN_NAME)),
+        Shell::Fish => config_dir
+            .join("fish")
+            .join("completions")
+            .join(format!("{}.fish", BIN_NAME)),
+        other => {
+            return Err(AppError::ValidationError(format!(
+                "Installing completions for {} is not supported",
+                other
+            )));
+        }
+    };
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file: File = File::create(&path)?;
+    generate(shell, command, BIN_NAME, &mut file);
+
+    println!(
+        "{} Installed {} completions to {}",
+        "[SUCCESS]".green().bold(),
+        shell,
+        path.display().to_string().cyan()
+    );
+    if shell == Shell::Zsh {
+        println!(
+            "{} Add {} to ~/.zshrc before compinit if it isn't there yet",
+            "[INFO]".blue().bold(),
+            "fpath=(~/.zfunc $fpath)".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+fn create_private_file(path: &Path) -> Result<File, AppError> {
+    let mut options: OpenOptions = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    Ok(options.open(path)?)
+}
+
+fn prompt(message: &str) -> Result<String, AppError> {
+    print!("{}", message.bold());
+    io::stdout().flush()?;
+    let mut answer: String = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+fn confirm(question: &str) -> Result<bool, AppError> {
+    let answer: String = prompt(&format!("{} [y/N] ", question))?;
+    Ok(matches!(answer.as_str(), "y" | "Y" | "yes"))
+}
+
+/// Reads a line with terminal echo turned off where `stty` is available
+fn prompt_secret(message: &str) -> Result<String, AppError> {
+    let set_echo = |flag: &str| {
+        Command::new("stty")
+            .arg(flag)
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+
+    let hidden: bool = set_echo("-echo");
+    let answer: Result<String, AppError> = prompt(message);
+    if hidden {
+        set_echo("echo");
+        println!();
+    }
+    answer
+}
@@ -6,6 +6,8 @@ use which::which;
 
 pub const HUT_COMMAND: &str = "hut";
 pub const PASTE_COMMAND: &str = "paste";
+pub const PAGES_COMMAND: &str = "pages";
+pub const TAR_COMMAND: &str = "tar";
 
 // ANSI color codes
 const GREEN: &str = "\x1b[32m";
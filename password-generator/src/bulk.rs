@@ -0,0 +1,123 @@
+//! Streaming generation of large password sets to a file.
+//!
+//! Passwords are generated and written in batches so that only one batch
+//! is held in memory at a time, with a progress line on stderr. With
+//! `--encrypt` the file is written in age format instead of plain text.
+
+use std::fs::{File, OpenOptions};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use crate::encrypt::{self, Sink};
+use crate::{Config, OutputType, PasswordGenerator, Source};
+
+/// random.org accepts at most 100 passwords per request.
+const RANDOM_ORG_BATCH_SIZE: usize = 100;
+const LOCAL_BATCH_SIZE: usize = 1000;
+
+/// Generates `config.count` passwords into `path` in the configured format.
+pub async fn write_to_file(config: &Config, path: &Path) -> Result<()> {
+        if config.r#type == OutputType::Alfred {
+                bail!("Alfred output cannot be written to a file; use plain, json or ndjson");
+        }
+
+        let batch_size: usize = match config.source {
+                Source::RandomOrg => RANDOM_ORG_BATCH_SIZE,
+                Source::Local => LOCAL_BATCH_SIZE,
+        };
+        let generator: PasswordGenerator =
+                PasswordGenerator::new(config.clone())?;
+        let encryptor: Option<age::Encryptor> =
+                encrypt::encryptor(config.encrypt.as_ref())?;
+        let mut writer: Sink = Sink::new(create_file(path)?, encryptor)?;
+        let show_progress: bool = std::io::stderr().is_terminal();
+
+        if config.r#type == OutputType::Json {
+                writer.write_all(b"[")?;
+        }
+
+        let mut written: usize = 0;
+        while written < config.count {
+                let count: usize = batch_size.min(config.count - written);
+                let passwords: Vec<String> = generator
+                        .with_count(count)
+                        .generate_passwords()
+                        .await?;
+                let output: String = match config.split_shamir {
+                        Some(spec) => crate::format_split_output(
+                                passwords,
+                                spec,
+                                config.r#type,
+                        )?,
+                        None => crate::format_output(passwords, config.r#type)?,
+                };
+                write_batch(&mut writer, &output, config, written == 0)?;
+
+                written += count;
+                if show_progress {
+                        eprint!(
+                                "\rWrote {}/{} passwords",
+                                written, config.count
+                        );
+                }
+        }
+
+        match config.r#type {
+                OutputType::Json => writer.write_all(b"]\n")?,
+                _ => writer.write_all(b"\n")?,
+        }
+        writer.finish().with_context(|| {
+                format!("Failed to write {}", path.display())
+        })?;
+
+        if show_progress {
+                eprintln!();
+        }
+        Ok(())
+}
+
+/// Appends one formatted batch, joining it to the previous one the way the
+/// format expects: a newline, a blank line between share groups, or a comma
+/// between JSON array elements.
+fn write_batch(
+        writer: &mut impl Write,
+        output: &str,
+        config: &Config,
+        first: bool,
+) -> Result<()> {
+        let (separator, body): (&str, &str) = match config.r#type {
+                OutputType::Json => (
+                        ",",
+                        output.strip_prefix('[')
+                                .and_then(|o| o.strip_suffix(']'))
+                                .unwrap_or(output),
+                ),
+                OutputType::Plain if config.split_shamir.is_some() => {
+                        ("\n\n", output)
+                }
+                _ => ("\n", output),
+        };
+        if body.is_empty() {
+                return Ok(());
+        }
+        if !first {
+                writer.write_all(separator.as_bytes())?;
+        }
+        writer.write_all(body.as_bytes())?;
+        Ok(())
+}
+
+/// Creates the output file, readable only by the owner on Unix.
+fn create_file(path: &Path) -> Result<File> {
+        let mut options: OpenOptions = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+        }
+        options.open(path)
+                .with_context(|| format!("Failed to create {}", path.display()))
+}
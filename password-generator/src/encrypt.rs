@@ -0,0 +1,174 @@
+//! age encryption of files written with `--out`.
+//!
+//! Files are written in the standard age format, so they can be opened
+//! with `age --decrypt` or any other age implementation.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::iter;
+
+use age::secrecy::SecretString;
+use age::stream::StreamWriter;
+use age::x25519;
+use anyhow::{bail, Context, Result};
+
+// How the output file is encrypted
+#[derive(Debug, Clone)]
+pub enum Encryption {
+        Recipient(x25519::Recipient),
+        Passphrase,
+}
+
+impl std::str::FromStr for Encryption {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                if s.eq_ignore_ascii_case("passphrase") {
+                        return Ok(Encryption::Passphrase);
+                }
+                s.parse::<x25519::Recipient>()
+                        .map(Encryption::Recipient)
+                        .map_err(|e| {
+                                format!(
+                                        "Invalid age recipient: {}. Must be an age1... public key or 'passphrase'",
+                                        e
+                                )
+                        })
+        }
+}
+
+/// Builds the encryptor for `encryption`, prompting for a passphrase if
+/// needed. Called before the output file is created so that a failed
+/// prompt leaves any existing file alone.
+pub fn encryptor(
+        encryption: Option<&Encryption>,
+) -> Result<Option<age::Encryptor>> {
+        Ok(match encryption {
+                None => None,
+                Some(Encryption::Recipient(recipient)) => {
+                        Some(age::Encryptor::with_recipients(iter::once(
+                                recipient as &dyn age::Recipient,
+                        ))?)
+                }
+                Some(Encryption::Passphrase) => Some(
+                        age::Encryptor::with_user_passphrase(read_passphrase()?),
+                ),
+        })
+}
+
+/// Destination of `--out`, encrypting on the fly when requested.
+pub enum Sink {
+        Plain(BufWriter<File>),
+        Encrypted(StreamWriter<BufWriter<File>>),
+}
+
+impl Sink {
+        /// Wraps `file`, encrypting with `encryptor` when there is one.
+        pub fn new(
+                file: File,
+                encryptor: Option<age::Encryptor>,
+        ) -> Result<Self> {
+                let writer: BufWriter<File> = BufWriter::new(file);
+                Ok(match encryptor {
+                        Some(encryptor) => {
+                                Sink::Encrypted(encryptor.wrap_output(writer)?)
+                        }
+                        None => Sink::Plain(writer),
+                })
+        }
+
+        /// Flushes everything, writing the final age chunk when encrypting.
+        pub fn finish(self) -> io::Result<()> {
+                match self {
+                        Sink::Plain(mut writer) => writer.flush(),
+                        Sink::Encrypted(writer) => writer.finish()?.flush(),
+                }
+        }
+}
+
+impl Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                match self {
+                        Sink::Plain(writer) => writer.write(buf),
+                        Sink::Encrypted(writer) => writer.write(buf),
+                }
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+                match self {
+                        Sink::Plain(writer) => writer.flush(),
+                        Sink::Encrypted(writer) => writer.flush(),
+                }
+        }
+}
+
+/// Asks for the passphrase twice on the terminal.
+fn read_passphrase() -> Result<SecretString> {
+        let passphrase: String =
+                rpassword::prompt_password("Passphrase for the output file: ")
+                        .context("Failed to read the passphrase")?;
+        if passphrase.is_empty() {
+                bail!("The passphrase must not be empty");
+        }
+        let confirmation: String =
+                rpassword::prompt_password("Confirm passphrase: ")
+                        .context("Failed to read the passphrase")?;
+        if passphrase != confirmation {
+                bail!("Passphrases do not match");
+        }
+        Ok(SecretString::from(passphrase))
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+        use std::io::Read;
+
+        #[test]
+        fn test_parse_encryption() {
+                let identity: x25519::Identity = x25519::Identity::generate();
+                let recipient: String = identity.to_public().to_string();
+                assert!(matches!(
+                        recipient.parse::<Encryption>(),
+                        Ok(Encryption::Recipient(_))
+                ));
+                assert!(matches!(
+                        "Passphrase".parse::<Encryption>(),
+                        Ok(Encryption::Passphrase)
+                ));
+                assert!("age1nope".parse::<Encryption>().is_err());
+        }
+
+        #[test]
+        fn test_recipient_round_trip() {
+                let identity: x25519::Identity = x25519::Identity::generate();
+                let path: std::path::PathBuf =
+                        std::env::temp_dir().join(format!(
+                                "password-generator-encrypt-{}.age",
+                                std::process::id()
+                        ));
+
+                let encryption: Encryption =
+                        Encryption::Recipient(identity.to_public());
+                let mut sink: Sink = Sink::new(
+                        File::create(&path).unwrap(),
+                        encryptor(Some(&encryption)).unwrap(),
+                )
+                .unwrap();
+                sink.write_all(b"hunter2\ncorrect-horse\n").unwrap();
+                sink.finish().unwrap();
+
+                let encrypted: Vec<u8> = std::fs::read(&path).unwrap();
+                std::fs::remove_file(&path).unwrap();
+                assert!(encrypted.starts_with(b"age-encryption.org/v1"));
+
+                let mut plaintext: String = String::new();
+                age::Decryptor::new(&encrypted[..])
+                        .unwrap()
+                        .decrypt(iter::once(&identity as &dyn age::Identity))
+                        .unwrap()
+                        .read_to_string(&mut plaintext)
+                        .unwrap();
+                assert_eq!(plaintext, "hunter2\ncorrect-horse\n");
+        }
+}
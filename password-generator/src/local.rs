@@ -0,0 +1,396 @@
+//! Offline password generation from the OS random source.
+//!
+//! Passwords are drawn uniformly from a configurable alphabet. Minimum
+//! digit and uppercase counts are met by placing the required characters
+//! first and shuffling the whole password afterwards. `--require` rules are
+//! checked afterwards and failing candidates are regenerated. PINs, hex and
+//! base64 tokens and UUIDs come from the same source.
+
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::{rngs::OsRng, TryRngCore};
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>?/~";
+const AMBIGUOUS: &str = "0Oo1lI|";
+const HEX_DIGITS: &str = "0123456789abcdef";
+
+/// Candidates drawn per password before `--require` rules are given up on.
+const MAX_REQUIRE_ATTEMPTS: usize = 1000;
+
+/// A `--require SET:MIN` rule: at least `min` characters from `chars`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+        pub chars: String,
+        pub min: usize,
+}
+
+impl FromStr for Requirement {
+        type Err = String;
+
+        /// SET is `lower`, `upper`, `digit`, `symbol` or a literal list of
+        /// characters, e.g. `symbol:2` or `!@#:1`.
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (set, min): (&str, &str) = s.rsplit_once(':').ok_or_else(|| {
+                        format!("Invalid requirement: {}. Expected SET:MIN", s)
+                })?;
+                let min: usize = min.parse().map_err(|_| {
+                        format!("Invalid minimum in requirement: {}", s)
+                })?;
+                let chars: &str = match set {
+                        "" => {
+                                return Err(format!(
+                                        "Invalid requirement: {}. SET is empty",
+                                        s
+                                ))
+                        }
+                        "lower" => LOWERCASE,
+                        "upper" => UPPERCASE,
+                        "digit" => DIGITS,
+                        "symbol" => SYMBOLS,
+                        literal => literal,
+                };
+                Ok(Requirement {
+                        chars: chars.to_string(),
+                        min,
+                })
+        }
+}
+
+impl Requirement {
+        fn is_met(&self, password: &[char]) -> bool {
+                password.iter().filter(|c| self.chars.contains(**c)).count()
+                        >= self.min
+        }
+}
+
+/// Character set and composition rules for local generation.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+        pub symbols: bool,
+        pub no_ambiguous: bool,
+        pub digits_min: usize,
+        pub upper_min: usize,
+        pub exclude: String,
+        pub require: Vec<Requirement>,
+}
+
+impl Policy {
+        /// Base characters plus any characters a requirement asks for, so a
+        /// rule such as `symbol:1` works without `--symbols`.
+        fn alphabet(&self) -> Vec<char> {
+                let mut alphabet: String =
+                        format!("{}{}{}", LOWERCASE, UPPERCASE, DIGITS);
+                if self.symbols {
+                        alphabet.push_str(SYMBOLS);
+                }
+                for requirement in &self.require {
+                        alphabet.push_str(&requirement.chars);
+                }
+
+                let mut chars: Vec<char> = Vec::new();
+                for c in self.filter(&alphabet) {
+                        if !chars.contains(&c) {
+                                chars.push(c);
+                        }
+                }
+                chars
+        }
+
+        fn filter(&self, chars: &str) -> Vec<char> {
+                chars.chars()
+                        .filter(|c| {
+                                !self.no_ambiguous || !AMBIGUOUS.contains(*c)
+                        })
+                        .filter(|c| !self.exclude.contains(*c))
+                        .collect()
+        }
+
+        fn check(&self, length: usize) -> Result<()> {
+                if self.digits_min + self.upper_min > length {
+                        bail!(
+                                "Password length {} is too short for {} digit(s) and {} uppercase letter(s)",
+                                length,
+                                self.digits_min,
+                                self.upper_min
+                        );
+                }
+                if self.digits_min > 0 && self.filter(DIGITS).is_empty() {
+                        bail!("--digits-min is set but every digit is excluded");
+                }
+                if self.upper_min > 0 && self.filter(UPPERCASE).is_empty() {
+                        bail!("--upper-min is set but every uppercase letter is excluded");
+                }
+                if length > 0 && self.alphabet().is_empty() {
+                        bail!("Every character is excluded");
+                }
+
+                let required: usize = self.require.iter().map(|r| r.min).sum();
+                if required > length {
+                        bail!(
+                                "Password length {} is too short for {} required character(s)",
+                                length,
+                                required
+                        );
+                }
+                for requirement in &self.require {
+                        if requirement.min > 0
+                                && self.filter(&requirement.chars).is_empty()
+                        {
+                                bail!(
+                                        "Every character required by '{}:{}' is excluded",
+                                        requirement.chars,
+                                        requirement.min
+                                );
+                        }
+                }
+                Ok(())
+        }
+}
+
+/// Generates `count` passwords of `length` characters under `policy`.
+pub fn generate(
+        count: usize,
+        length: usize,
+        policy: &Policy,
+) -> Result<Vec<String>> {
+        policy.check(length)?;
+
+        let alphabet: Vec<char> = policy.alphabet();
+        let digits: Vec<char> = policy.filter(DIGITS);
+        let uppercase: Vec<char> = policy.filter(UPPERCASE);
+
+        let candidate = || -> Result<Vec<char>> {
+                let mut password: Vec<char> = Vec::with_capacity(length);
+                for _ in 0..policy.digits_min {
+                        password.push(pick(&digits)?);
+                }
+                for _ in 0..policy.upper_min {
+                        password.push(pick(&uppercase)?);
+                }
+                while password.len() < length {
+                        password.push(pick(&alphabet)?);
+                }
+                shuffle(&mut password)?;
+                Ok(password)
+        };
+
+        (0..count)
+                .map(|_| {
+                        for _ in 0..MAX_REQUIRE_ATTEMPTS {
+                                let password: Vec<char> = candidate()?;
+                                if policy.require.iter().all(|r| r.is_met(&password)) {
+                                        return Ok(password.into_iter().collect());
+                                }
+                        }
+                        bail!(
+                                "No password met the --require rules after {} attempts; try a longer length or fewer rules",
+                                MAX_REQUIRE_ATTEMPTS
+                        )
+                })
+                .collect()
+}
+
+/// Numeric PIN of `length` digits.
+pub fn pin(length: usize) -> Result<String> {
+        from_alphabet(DIGITS, length)
+}
+
+/// Lowercase hex token of `length` characters.
+pub fn hex(length: usize) -> Result<String> {
+        from_alphabet(HEX_DIGITS, length)
+}
+
+/// URL-safe base64 token of `length` characters.
+pub fn base64(length: usize) -> Result<String> {
+        let mut bytes: Vec<u8> = vec![0; length.div_ceil(4) * 3];
+        fill(&mut bytes)?;
+        let mut token: String = URL_SAFE_NO_PAD.encode(&bytes);
+        token.truncate(length);
+        Ok(token)
+}
+
+/// Random (version 4) UUID in its hyphenated form.
+pub fn uuid() -> Result<String> {
+        let mut bytes: [u8; 16] = [0; 16];
+        fill(&mut bytes)?;
+        bytes[6] = (bytes[6] & 0x0f) | 0x40;
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(format!(
+                "{}-{}-{}-{}-{}",
+                &hex[0..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..32]
+        ))
+}
+
+fn from_alphabet(alphabet: &str, length: usize) -> Result<String> {
+        let chars: Vec<char> = alphabet.chars().collect();
+        (0..length).map(|_| pick(&chars)).collect()
+}
+
+fn fill(bytes: &mut [u8]) -> Result<()> {
+        OsRng.try_fill_bytes(bytes)
+                .context("Failed to read from the OS random source")
+}
+
+fn pick(chars: &[char]) -> Result<char> {
+        Ok(chars[random_index(chars.len())?])
+}
+
+/// Fisher-Yates shuffle, so required characters land anywhere.
+fn shuffle(chars: &mut [char]) -> Result<()> {
+        for i in (1..chars.len()).rev() {
+                chars.swap(i, random_index(i + 1)?);
+        }
+        Ok(())
+}
+
+/// Returns a uniform index below `bound`, rejecting the values that would
+/// bias a plain modulo.
+fn random_index(bound: usize) -> Result<usize> {
+        let bound: u32 =
+                u32::try_from(bound).context("Alphabet is too large")?;
+        let zone: u32 = u32::MAX - u32::MAX % bound;
+        loop {
+                let value: u32 = OsRng
+                        .try_next_u32()
+                        .context("Failed to read from the OS random source")?;
+                if value < zone {
+                        return Ok((value % bound) as usize);
+                }
+        }
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        #[test]
+        fn test_generate_respects_count_and_length() {
+                let passwords: Vec<String> =
+                        generate(5, 16, &Policy::default()).unwrap();
+                assert_eq!(passwords.len(), 5);
+                assert!(passwords.iter().all(|p| p.chars().count() == 16));
+                assert!(passwords
+                        .iter()
+                        .all(|p| p.chars().all(char::is_alphanumeric)));
+        }
+
+        #[test]
+        fn test_generate_meets_minimums_without_ambiguous() {
+                let policy: Policy = Policy {
+                        symbols: true,
+                        no_ambiguous: true,
+                        digits_min: 3,
+                        upper_min: 4,
+                        ..Policy::default()
+                };
+                for password in generate(20, 8, &policy).unwrap() {
+                        assert!(password
+                                .chars()
+                                .filter(char::is_ascii_digit)
+                                .count()
+                                >= 3);
+                        assert!(password
+                                .chars()
+                                .filter(char::is_ascii_uppercase)
+                                .count()
+                                >= 4);
+                        assert!(!password
+                                .chars()
+                                .any(|c| AMBIGUOUS.contains(c)));
+                }
+        }
+
+        #[test]
+        fn test_generate_rejects_impossible_policy() {
+                let policy: Policy = Policy {
+                        digits_min: 5,
+                        upper_min: 5,
+                        ..Policy::default()
+                };
+                assert!(generate(1, 8, &policy).is_err());
+        }
+
+        #[test]
+        fn test_generate_honours_exclude_and_require() {
+                let policy: Policy = Policy {
+                        exclude: "abc123".to_string(),
+                        require: vec![
+                                "symbol:2".parse().unwrap(),
+                                "_-:1".parse().unwrap(),
+                        ],
+                        ..Policy::default()
+                };
+                for password in generate(20, 12, &policy).unwrap() {
+                        assert!(!password
+                                .chars()
+                                .any(|c| "abc123".contains(c)));
+                        assert!(password
+                                .chars()
+                                .filter(|c| SYMBOLS.contains(*c))
+                                .count()
+                                >= 2);
+                        assert!(password.chars().any(|c| "_-".contains(c)));
+                }
+        }
+
+        #[test]
+        fn test_requirement_parsing_and_infeasible_rules() {
+                let requirement: Requirement = "digit:3".parse().unwrap();
+                assert_eq!(requirement.chars, DIGITS);
+                assert_eq!(requirement.min, 3);
+                assert_eq!(
+                        "a:b:2".parse::<Requirement>().unwrap().chars,
+                        "a:b"
+                );
+                assert!("digit".parse::<Requirement>().is_err());
+                assert!(":2".parse::<Requirement>().is_err());
+
+                let excluded: Policy = Policy {
+                        exclude: DIGITS.to_string(),
+                        require: vec![requirement],
+                        ..Policy::default()
+                };
+                assert!(generate(1, 8, &excluded).is_err());
+
+                let too_long: Policy = Policy {
+                        require: vec![
+                                "lower:5".parse().unwrap(),
+                                "upper:5".parse().unwrap(),
+                        ],
+                        ..Policy::default()
+                };
+                assert!(generate(1, 8, &too_long).is_err());
+        }
+
+        #[test]
+        fn test_tokens_have_requested_shape() {
+                let pin: String = pin(6).unwrap();
+                assert_eq!(pin.len(), 6);
+                assert!(pin.chars().all(|c| c.is_ascii_digit()));
+
+                let hex: String = hex(32).unwrap();
+                assert_eq!(hex.len(), 32);
+                assert!(hex.chars().all(|c| HEX_DIGITS.contains(c)));
+
+                for length in [1, 7, 43] {
+                        assert_eq!(base64(length).unwrap().len(), length);
+                }
+
+                let uuid: String = uuid().unwrap();
+                assert_eq!(uuid.len(), 36);
+                assert_eq!(&uuid[14..15], "4");
+                assert!("89ab".contains(&uuid[19..20]));
+        }
+}
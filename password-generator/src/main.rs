@@ -1,14 +1,32 @@
 //! Password generator that creates cryptographically secure passwords.
 //!
-//! Generates passwords using random.org API.
+//! Generates passwords using random.org API, or locally from the OS
+//! random source.
 
+mod bulk;
+mod encrypt;
+mod local;
+mod profile;
+mod qr;
+mod shamir;
+mod strength;
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use encrypt::Encryption;
+use local::{Policy, Requirement};
+use shamir::{Share, SplitSpec};
+use strength::Strength;
+
 // Constants
 const DEFAULT_PASSWORD_COUNT: usize = 10;
 const DEFAULT_PASSWORD_LENGTH: usize = 20;
@@ -19,12 +37,18 @@ const POOL_IDLE_TIMEOUT_SECONDS: u64 = 30;
 const POOL_MAX_IDLE_PER_HOST: usize = 2;
 const INITIAL_RETRY_DELAY_MS: u64 = 100;
 const CLIPBOARD_SUBTITLE: &str = "Click to copy to clipboard";
+const MAX_ENTROPY_ATTEMPTS: u32 = 10;
+const QUOTA_URL: &str = "https://www.random.org/quota/?format=plain";
+// random.org charges bits per character; log2(62) rounds up to 6
+const BITS_PER_CHARACTER: i64 = 6;
 
 // Output type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OutputType {
         Plain,
         Alfred,
+        Json,
+        Ndjson,
 }
 
 impl std::str::FromStr for OutputType {
@@ -34,8 +58,60 @@ impl std::str::FromStr for OutputType {
                 match s.to_lowercase().as_str() {
                         "plain" => Ok(OutputType::Plain),
                         "alfred" => Ok(OutputType::Alfred),
+                        "json" => Ok(OutputType::Json),
+                        "ndjson" => Ok(OutputType::Ndjson),
+                        _ => Err(format!(
+                                "Invalid output type: {}. Must be 'plain', 'alfred', 'json' or 'ndjson'",
+                                s
+                        )),
+                }
+        }
+}
+
+// Password source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+        Local,
+        RandomOrg,
+}
+
+impl std::str::FromStr for Source {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_lowercase().as_str() {
+                        "local" => Ok(Source::Local),
+                        "random-org" => Ok(Source::RandomOrg),
+                        _ => Err(format!(
+                                "Invalid source: {}. Must be 'local' or 'random-org'",
+                                s
+                        )),
+                }
+        }
+}
+
+// Generation mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+        Password,
+        Pin,
+        Hex,
+        Base64,
+        Uuid,
+}
+
+impl std::str::FromStr for Mode {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s.to_lowercase().as_str() {
+                        "password" => Ok(Mode::Password),
+                        "pin" => Ok(Mode::Pin),
+                        "hex" => Ok(Mode::Hex),
+                        "base64" => Ok(Mode::Base64),
+                        "uuid" => Ok(Mode::Uuid),
                         _ => Err(format!(
-                                "Invalid output type: {}. Must be 'plain' or 'alfred'",
+                                "Invalid mode: {}. Must be 'password', 'pin', 'hex', 'base64' or 'uuid'",
                                 s
                         )),
                 }
@@ -60,11 +136,144 @@ struct Config {
         #[arg(value_parser = clap::value_parser!(OutputType))]
         r#type: OutputType,
 
+        #[arg(
+                long,
+                default_value = "password",
+                help = "What to generate: password, pin, hex, base64 or uuid (all but password are generated locally)"
+        )]
+        #[arg(value_parser = clap::value_parser!(Mode))]
+        mode: Mode,
+
+        #[arg(
+                long,
+                default_value = "random-org",
+                help = "Where passwords come from: local or random-org"
+        )]
+        #[arg(value_parser = clap::value_parser!(Source))]
+        source: Source,
+
+        #[arg(long, help = "Include symbols (local source only)")]
+        symbols: bool,
+
+        #[arg(
+                long,
+                help = "Leave out look-alike characters such as 0/O and 1/l (local source only)"
+        )]
+        no_ambiguous: bool,
+
+        #[arg(
+                long,
+                default_value_t = 0,
+                help = "Minimum number of digits (local source only)"
+        )]
+        digits_min: usize,
+
+        #[arg(
+                long,
+                default_value_t = 0,
+                help = "Minimum number of uppercase letters (local source only)"
+        )]
+        upper_min: usize,
+
+        #[arg(
+                long,
+                value_name = "CHARS",
+                help = "Never use any of CHARS (local source only)"
+        )]
+        exclude: Option<String>,
+
+        #[arg(
+                long,
+                value_name = "SET:MIN",
+                help = "Require at least MIN characters from SET: lower, upper, digit, symbol or literal characters; repeatable (local source only)"
+        )]
+        #[arg(value_parser = clap::value_parser!(Requirement))]
+        require: Vec<Requirement>,
+
+        #[arg(
+                long,
+                value_name = "BITS",
+                help = "Regenerate passwords whose estimated entropy is below BITS"
+        )]
+        min_entropy: Option<f64>,
+
+        #[arg(
+                long,
+                help = "Score passwords read from stdin, one per line, instead of generating"
+        )]
+        analyze: bool,
+
+        #[arg(
+                long,
+                help = "Fail instead of generating locally when random.org is out of quota or unreachable"
+        )]
+        no_fallback: bool,
+
+        #[arg(
+                long,
+                value_name = "FILE",
+                conflicts_with_all = ["qr", "analyze"],
+                help = "Write passwords to FILE in batches instead of stdout"
+        )]
+        out: Option<PathBuf>,
+
+        #[arg(
+                long,
+                requires = "out",
+                value_name = "RECIPIENT",
+                help = "Encrypt the --out file with age, for an age1... public key or a prompted passphrase ('passphrase')"
+        )]
+        #[arg(value_parser = clap::value_parser!(Encryption))]
+        encrypt: Option<Encryption>,
+
+        #[arg(
+                long,
+                value_name = "NAME",
+                help = "Load defaults from a profile in ~/.config/password-generator/config.toml"
+        )]
+        profile: Option<String>,
+
         #[arg(long, default_value_t = DEFAULT_API_TIMEOUT_SECONDS, help = "Timeout for API requests in seconds")]
         api_timeout: u64,
 
         #[arg(long, default_value_t = DEFAULT_MAX_RETRIES, help = "Maximum number of retries for API requests")]
         max_retries: u32,
+
+        #[arg(
+                long,
+                value_name = "N:K",
+                help = "Split each password into N Shamir shares, any K of which recover it"
+        )]
+        #[arg(value_parser = clap::value_parser!(SplitSpec))]
+        split_shamir: Option<SplitSpec>,
+
+        #[arg(
+                long,
+                help = "Also print the first password as a terminal QR code"
+        )]
+        qr: bool,
+
+        #[arg(
+                long,
+                requires = "qr",
+                value_name = "SSID",
+                help = "Encode the QR code as a Wi-Fi network login for SSID"
+        )]
+        wifi_ssid: Option<String>,
+
+        #[command(subcommand)]
+        command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum Command {
+        #[command(about = "Recover a password from Shamir shares")]
+        Combine {
+                #[arg(
+                        help = "Shares to combine (read from stdin, one per line, when omitted)"
+                )]
+                shares: Vec<String>,
+        },
 }
 
 // Data structures
@@ -80,10 +289,23 @@ struct AlfredOutput {
         items: Vec<AlfredItem>,
 }
 
+#[derive(Debug, Serialize)]
+struct PasswordRecord {
+        password: String,
+        length: usize,
+        entropy: f64,
+        charset: Vec<&'static str>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        shares: Vec<String>,
+}
+
 // Password generation
 struct PasswordGenerator {
         config: Config,
         http_client: Client,
+        // Set once random.org has failed, so later batches go straight to
+        // the local generator instead of warning again
+        fell_back: Arc<AtomicBool>,
 }
 
 impl PasswordGenerator {
@@ -93,11 +315,122 @@ impl PasswordGenerator {
                 Ok(Self {
                         config,
                         http_client,
+                        fell_back: Arc::new(AtomicBool::new(false)),
                 })
         }
 
+        /// Same generator producing `count` passwords per call, for batching.
+        fn with_count(&self, count: usize) -> Self {
+                let mut config: Config = self.config.clone();
+                config.count = count;
+                Self {
+                        config,
+                        http_client: self.http_client.clone(),
+                        fell_back: Arc::clone(&self.fell_back),
+                }
+        }
+
         async fn generate_passwords(&self) -> Result<Vec<String>> {
-                generate_via_api(self).await
+                let Some(min_entropy) = self.config.min_entropy else {
+                        return self.generate_batch().await;
+                };
+
+                let mut accepted: Vec<String> =
+                        Vec::with_capacity(self.config.count);
+                for _ in 0..MAX_ENTROPY_ATTEMPTS {
+                        for password in self.generate_batch().await? {
+                                if accepted.len() < self.config.count
+                                        && strength::estimate(&password).bits
+                                                >= min_entropy
+                                {
+                                        accepted.push(password);
+                                }
+                        }
+                        if accepted.len() == self.config.count {
+                                return Ok(accepted);
+                        }
+                }
+
+                Err(anyhow::anyhow!(
+                        "Could not generate {} password(s) with at least {} bits of entropy after {} attempts; try a longer length",
+                        self.config.count,
+                        min_entropy,
+                        MAX_ENTROPY_ATTEMPTS
+                ))
+        }
+
+        async fn generate_batch(&self) -> Result<Vec<String>> {
+                let token: fn(usize) -> Result<String> = match self.config.mode
+                {
+                        Mode::Password => {
+                                return self.generate_password_batch().await
+                        }
+                        Mode::Pin => local::pin,
+                        Mode::Hex => local::hex,
+                        Mode::Base64 => local::base64,
+                        Mode::Uuid => |_| local::uuid(),
+                };
+                (0..self.config.count)
+                        .map(|_| token(self.config.length))
+                        .collect()
+        }
+
+        async fn generate_password_batch(&self) -> Result<Vec<String>> {
+                match self.config.source {
+                        Source::Local => local::generate(
+                                self.config.count,
+                                self.config.length,
+                                &policy_from_config(&self.config),
+                        ),
+                        Source::RandomOrg => {
+                                let result: Result<Vec<String>> = if self
+                                        .fell_back
+                                        .load(Ordering::Relaxed)
+                                {
+                                        Err(anyhow::anyhow!(
+                                                "random.org is unavailable"
+                                        ))
+                                } else {
+                                        match check_quota(self).await {
+                                                Ok(()) => {
+                                                        generate_via_api(self)
+                                                                .await
+                                                }
+                                                Err(e) => Err(e),
+                                        }
+                                };
+                                match result {
+                                        Err(e) if !self.config.no_fallback => {
+                                                if !self.fell_back.swap(
+                                                        true,
+                                                        Ordering::Relaxed,
+                                                ) {
+                                                        eprintln!(
+                                                                "Warning: {}; generating locally instead",
+                                                                e
+                                                        );
+                                                }
+                                                local::generate(
+                                                        self.config.count,
+                                                        self.config.length,
+                                                        &Policy::default(),
+                                                )
+                                        }
+                                        result => result,
+                                }
+                        }
+                }
+        }
+}
+
+fn policy_from_config(config: &Config) -> Policy {
+        Policy {
+                symbols: config.symbols,
+                no_ambiguous: config.no_ambiguous,
+                digits_min: config.digits_min,
+                upper_min: config.upper_min,
+                exclude: config.exclude.clone().unwrap_or_default(),
+                require: config.require.clone(),
         }
 }
 
@@ -149,6 +482,50 @@ async fn generate_via_api(
         }))
 }
 
+/// Fails when random.org cannot be reached or the remaining quota does not
+/// cover this request, so no time is spent on retries that cannot succeed.
+async fn check_quota(generator: &PasswordGenerator) -> Result<()> {
+        let response: reqwest::Response = generator
+                .http_client
+                .get(QUOTA_URL)
+                .send()
+                .await
+                .context("random.org is unreachable")?;
+        if !response.status().is_success() {
+                return Err(anyhow::anyhow!(
+                        "random.org quota check returned status: {}",
+                        response.status()
+                ));
+        }
+
+        let body: String = response
+                .text()
+                .await
+                .context("Failed to read quota response")?;
+        let remaining: i64 = parse_quota(&body)?;
+        let required: i64 = required_bits(&generator.config);
+        if remaining < required {
+                return Err(anyhow::anyhow!(
+                        "random.org quota is exhausted ({} bits left, {} needed)",
+                        remaining,
+                        required
+                ));
+        }
+        Ok(())
+}
+
+fn parse_quota(body: &str) -> Result<i64> {
+        body.trim().parse().with_context(|| {
+                format!("Invalid quota response: {}", body.trim())
+        })
+}
+
+fn required_bits(config: &Config) -> i64 {
+        let characters: i64 =
+                (config.count as i64).saturating_mul(config.length as i64);
+        characters.saturating_mul(BITS_PER_CHARACTER)
+}
+
 fn build_api_url(config: &Config) -> String {
         format!(
                 "https://www.random.org/passwords/?num={}&len={}&format=plain&rnd=new",
@@ -212,9 +589,71 @@ fn format_output(
         match output_type {
                 OutputType::Plain => format_plain_text(passwords),
                 OutputType::Alfred => format_alfred_json(passwords),
+                OutputType::Json | OutputType::Ndjson => {
+                        let records: Vec<PasswordRecord> = passwords
+                                .into_iter()
+                                .filter(|password: &String| {
+                                        !password.is_empty()
+                                })
+                                .map(create_password_record)
+                                .collect();
+                        format_records(&records, output_type)
+                }
+        }
+}
+
+/// Serializes records as one JSON array, or one object per line for
+/// NDJSON. Only called for the JSON output types.
+fn format_records<T: Serialize>(
+        records: &[T],
+        output_type: OutputType,
+) -> Result<String> {
+        if output_type == OutputType::Ndjson {
+                records.iter()
+                        .map(|record: &T| {
+                                serde_json::to_string(record)
+                                        .context("Failed to serialize JSON")
+                        })
+                        .collect::<Result<Vec<String>>>()
+                        .map(|lines: Vec<String>| lines.join("\n"))
+        } else {
+                serde_json::to_string(records)
+                        .context("Failed to serialize JSON")
         }
 }
 
+/// Builds the JSON record for `password`. The entropy is the same zxcvbn
+/// estimate that `--min-entropy` and `--analyze` use.
+fn create_password_record(password: String) -> PasswordRecord {
+        let bits: f64 = strength::estimate(&password).bits;
+        PasswordRecord {
+                length: password.chars().count(),
+                entropy: (bits * 10.0).round() / 10.0,
+                charset: character_classes(&password),
+                password,
+                shares: Vec::new(),
+        }
+}
+
+/// Character classes that occur in `password`.
+fn character_classes(password: &str) -> Vec<&'static str> {
+        let class_of = |c: char| -> &'static str {
+                if c.is_ascii_lowercase() {
+                        "lowercase"
+                } else if c.is_ascii_uppercase() {
+                        "uppercase"
+                } else if c.is_ascii_digit() {
+                        "digits"
+                } else {
+                        "symbols"
+                }
+        };
+        ["lowercase", "uppercase", "digits", "symbols"]
+                .into_iter()
+                .filter(|name| password.chars().any(|c| class_of(c) == *name))
+                .collect()
+}
+
 fn format_plain_text(passwords: Vec<String>) -> Result<String> {
         Ok(passwords
                 .into_iter()
@@ -229,6 +668,128 @@ fn format_alfred_json(passwords: Vec<String>) -> Result<String> {
         serde_json::to_string(&output).context("Failed to serialize JSON")
 }
 
+fn format_split_output(
+        passwords: Vec<String>,
+        spec: SplitSpec,
+        output_type: OutputType,
+) -> Result<String> {
+        let mut groups: Vec<String> = Vec::with_capacity(passwords.len());
+        let mut items: Vec<AlfredItem> = Vec::new();
+        let mut records: Vec<PasswordRecord> = Vec::new();
+
+        for password in passwords.iter().filter(|p| !p.is_empty()) {
+                let shares: Vec<Share> =
+                        shamir::split(password.as_bytes(), spec)?;
+
+                match output_type {
+                        OutputType::Plain => {
+                                let mut lines: Vec<String> =
+                                        vec![password.clone()];
+                                lines.extend(shares
+                                        .iter()
+                                        .map(Share::to_string));
+                                groups.push(lines.join("\n"));
+                        }
+                        OutputType::Alfred => {
+                                items.extend(shares.iter().map(|share| {
+                                        AlfredItem {
+                                                title: share.to_string(),
+                                                subtitle: format!(
+                                                        "Share {} of {} ({} required)",
+                                                        share.x,
+                                                        spec.shares,
+                                                        spec.threshold
+                                                ),
+                                                arg: share.to_string(),
+                                        }
+                                }));
+                        }
+                        OutputType::Json | OutputType::Ndjson => {
+                                let mut record: PasswordRecord =
+                                        create_password_record(
+                                                password.clone(),
+                                        );
+                                record.shares = shares
+                                        .iter()
+                                        .map(Share::to_string)
+                                        .collect();
+                                records.push(record);
+                        }
+                }
+        }
+
+        match output_type {
+                OutputType::Plain => Ok(groups.join("\n\n")),
+                OutputType::Alfred => {
+                        serde_json::to_string(&AlfredOutput { items })
+                                .context("Failed to serialize JSON")
+                }
+                OutputType::Json | OutputType::Ndjson => {
+                        format_records(&records, output_type)
+                }
+        }
+}
+
+fn format_qr(passwords: &[String], wifi_ssid: Option<&str>) -> Result<String> {
+        let password: &String = passwords
+                .iter()
+                .find(|password| !password.is_empty())
+                .context("No password to encode as a QR code")?;
+        let payload: String = match wifi_ssid {
+                Some(ssid) => qr::wifi_payload(ssid, password),
+                None => password.clone(),
+        };
+        qr::render(&payload)
+}
+
+fn format_analysis(
+        strengths: Vec<Strength>,
+        output_type: OutputType,
+) -> Result<String> {
+        match output_type {
+                OutputType::Plain => Ok(strengths
+                        .iter()
+                        .map(strength::describe)
+                        .collect::<Vec<String>>()
+                        .join("\n")),
+                OutputType::Alfred => {
+                        let items: Vec<AlfredItem> = strengths
+                                .iter()
+                                .map(|strength: &Strength| AlfredItem {
+                                        title: format!(
+                                                "Score {}/4, ~{:.0} bits",
+                                                strength.score, strength.bits
+                                        ),
+                                        subtitle: format!(
+                                                "Cracked offline in {}. {}",
+                                                strength.crack_time,
+                                                strength.feedback.join(" ")
+                                        )
+                                        .trim_end()
+                                        .to_string(),
+                                        arg: strength::describe(strength),
+                                })
+                                .collect();
+                        serde_json::to_string(&AlfredOutput { items })
+                                .context("Failed to serialize JSON")
+                }
+                OutputType::Json | OutputType::Ndjson => {
+                        format_records(&strengths, output_type)
+                }
+        }
+}
+
+fn analyze_stdin() -> Result<Vec<Strength>> {
+        let mut input: String = String::new();
+        std::io::stdin()
+                .read_to_string(&mut input)
+                .context("Failed to read passwords from stdin")?;
+        Ok(input.lines()
+                .filter(|line| !line.is_empty())
+                .map(strength::estimate)
+                .collect())
+}
+
 fn create_alfred_items(passwords: Vec<String>) -> Vec<AlfredItem> {
         passwords
                 .into_iter()
@@ -242,6 +803,26 @@ fn create_alfred_items(passwords: Vec<String>) -> Vec<AlfredItem> {
 }
 
 // Validation
+fn check_source_options(config: &Config) -> Result<()> {
+        let has_charset_options: bool = config.symbols
+                || config.no_ambiguous
+                || config.digits_min > 0
+                || config.upper_min > 0
+                || config.exclude.is_some()
+                || !config.require.is_empty();
+        if config.source == Source::RandomOrg && has_charset_options {
+                anyhow::bail!(
+                        "--symbols, --no-ambiguous, --digits-min, --upper-min, --exclude and --require need --source local"
+                );
+        }
+        if config.mode != Mode::Password && has_charset_options {
+                anyhow::bail!(
+                        "--symbols, --no-ambiguous, --digits-min, --upper-min, --exclude and --require only apply to --mode password"
+                );
+        }
+        Ok(())
+}
+
 fn validate_config(config: &Config) {
         if config.count == 0 {
                 eprintln!("Warning: Password count is zero");
@@ -252,6 +833,11 @@ fn validate_config(config: &Config) {
         if config.length > 1000 {
                 eprintln!("Warning: Password length exceeds 1000 characters");
         }
+        if config.qr && config.r#type != OutputType::Plain {
+                eprintln!(
+                        "Warning: QR code output is only shown with --type plain"
+                );
+        }
 }
 
 // Main entry point
@@ -262,16 +848,77 @@ fn main() {
         }
 }
 
+fn combine_shares(shares: &[String]) -> Result<String> {
+        let lines: Vec<String> = if shares.is_empty() {
+                let mut input: String = String::new();
+                std::io::stdin()
+                        .read_to_string(&mut input)
+                        .context("Failed to read shares from stdin")?;
+                input.lines().map(String::from).collect()
+        } else {
+                shares.to_vec()
+        };
+
+        let shares: Vec<Share> = lines
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.parse())
+                .collect::<Result<_>>()?;
+        let secret: Vec<u8> = shamir::combine(&shares)?;
+
+        String::from_utf8(secret).context(
+                "Recovered secret is not valid UTF-8; check the shares",
+        )
+}
+
 async fn run_async() -> Result<()> {
-        let config: Config = Config::parse();
+        let matches: ArgMatches = Config::command().get_matches();
+        let mut config: Config = Config::from_arg_matches(&matches)?;
+        if let Some(profile) = profile::load(config.profile.as_deref())? {
+                profile.apply(&mut config, &matches)?;
+        }
+
+        if let Some(Command::Combine { shares }) = &config.command {
+                println!("{}", combine_shares(shares)?);
+                return Ok(());
+        }
+
+        if config.analyze {
+                println!(
+                        "{}",
+                        format_analysis(analyze_stdin()?, config.r#type)?
+                );
+                return Ok(());
+        }
+
         validate_config(&config);
+        check_source_options(&config)?;
+
+        if let Some(path) = &config.out {
+                return bulk::write_to_file(&config, path).await;
+        }
 
         let generator: PasswordGenerator =
                 PasswordGenerator::new(config.clone())?;
         let passwords: Vec<String> = generator.generate_passwords().await?;
-        let output: String = format_output(passwords, config.r#type)?;
+        let qr_code: Option<String> = if config.qr
+                && config.r#type == OutputType::Plain
+        {
+                Some(format_qr(&passwords, config.wifi_ssid.as_deref())?)
+        } else {
+                None
+        };
+        let output: String = match config.split_shamir {
+                Some(spec) => {
+                        format_split_output(passwords, spec, config.r#type)?
+                }
+                None => format_output(passwords, config.r#type)?,
+        };
 
         println!("{}", output);
+        if let Some(qr_code) = qr_code {
+                println!("\n{}", qr_code);
+        }
         Ok(())
 }
 
@@ -318,6 +965,43 @@ mod tests {
                 assert!(json.contains(CLIPBOARD_SUBTITLE));
         }
 
+        #[test]
+        fn test_format_json_and_ndjson() {
+                let passwords = vec!["abcD12".to_string(), "x!".to_string()];
+
+                let json = format_output(passwords.clone(), OutputType::Json)
+                        .unwrap();
+                let records: serde_json::Value =
+                        serde_json::from_str(&json).unwrap();
+                assert_eq!(records[0]["password"], "abcD12");
+                assert_eq!(records[0]["length"], 6);
+                let bits: f64 = strength::estimate("abcD12").bits;
+                assert_eq!(records[0]["entropy"], (bits * 10.0).round() / 10.0);
+                assert_eq!(
+                        records[0]["charset"],
+                        serde_json::json!(["lowercase", "uppercase", "digits"])
+                );
+                assert_eq!(
+                        records[1]["charset"],
+                        serde_json::json!(["lowercase", "symbols"])
+                );
+
+                let ndjson =
+                        format_output(passwords, OutputType::Ndjson).unwrap();
+                let lines: Vec<&str> = ndjson.lines().collect();
+                assert_eq!(lines.len(), 2);
+                assert!(lines.iter().all(|line| {
+                        serde_json::from_str::<serde_json::Value>(line).is_ok()
+                }));
+        }
+
+        #[test]
+        fn test_parse_quota() {
+                assert_eq!(parse_quota("1000000\n").unwrap(), 1_000_000);
+                assert_eq!(parse_quota("-2048").unwrap(), -2048);
+                assert!(parse_quota("<html>").is_err());
+        }
+
         #[test]
         fn test_is_valid_password_count() {
                 let passwords =
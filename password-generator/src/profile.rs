@@ -0,0 +1,201 @@
+//! Named default profiles loaded from a TOML config file.
+//!
+//! The file lives at `$XDG_CONFIG_HOME/password-generator/config.toml`
+//! (falling back to `~/.config`) and holds one table per profile:
+//!
+//! ```toml
+//! [profiles.work]
+//! length = 32
+//! symbols = true
+//! type = "json"
+//! ```
+//!
+//! The `default` profile applies when `--profile` is not given. Options
+//! passed on the command line always win over the profile.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::parser::ValueSource;
+use clap::ArgMatches;
+use serde::Deserialize;
+
+use crate::{Config, Mode, OutputType, Source};
+
+const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+        #[serde(default)]
+        profiles: HashMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Profile {
+        count: Option<usize>,
+        length: Option<usize>,
+        #[serde(rename = "type")]
+        output_type: Option<String>,
+        source: Option<String>,
+        mode: Option<String>,
+        symbols: Option<bool>,
+        no_ambiguous: Option<bool>,
+        digits_min: Option<usize>,
+        upper_min: Option<usize>,
+}
+
+fn config_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| {
+                        std::env::var_os("HOME")
+                                .map(|home| PathBuf::from(home).join(".config"))
+                })
+                .map(|dir| dir.join("password-generator").join("config.toml"))
+}
+
+/// Loads the profile called `name`, or the `default` profile when no name
+/// is given. Only an explicitly requested profile has to exist.
+pub fn load(name: Option<&str>) -> Result<Option<Profile>> {
+        let path: Option<PathBuf> = config_path().filter(|path| path.exists());
+        let Some(path) = path else {
+                if let Some(name) = name {
+                        bail!("Profile '{}' requested but no config file was found", name);
+                }
+                return Ok(None);
+        };
+
+        let content: String =
+                std::fs::read_to_string(&path).with_context(|| {
+                        format!("Failed to read {}", path.display())
+                })?;
+        let mut file: ConfigFile =
+                toml::from_str(&content).with_context(|| {
+                        format!("Invalid config file {}", path.display())
+                })?;
+
+        match name {
+                Some(name) => {
+                        file.profiles.remove(name).map(Some).with_context(
+                                || {
+                                        format!(
+                                                "No profile named '{}' in {}",
+                                                name,
+                                                path.display()
+                                        )
+                                },
+                        )
+                }
+                None => Ok(file.profiles.remove(DEFAULT_PROFILE)),
+        }
+}
+
+impl Profile {
+        /// Copies profile values into `config` for every option that was not
+        /// given on the command line.
+        pub fn apply(
+                self,
+                config: &mut Config,
+                matches: &ArgMatches,
+        ) -> Result<()> {
+                let unset = |id: &str| {
+                        matches.value_source(id)
+                                != Some(ValueSource::CommandLine)
+                };
+
+                if let Some(count) = self.count.filter(|_| unset("count")) {
+                        config.count = count;
+                }
+                if let Some(length) = self.length.filter(|_| unset("length")) {
+                        config.length = length;
+                }
+                if let Some(output_type) =
+                        self.output_type.filter(|_| unset("type"))
+                {
+                        config.r#type = parse::<OutputType>(&output_type)?;
+                }
+                if let Some(source) = self.source.filter(|_| unset("source")) {
+                        config.source = parse::<Source>(&source)?;
+                }
+                if let Some(mode) = self.mode.filter(|_| unset("mode")) {
+                        config.mode = parse::<Mode>(&mode)?;
+                }
+                if let Some(symbols) = self.symbols.filter(|_| unset("symbols"))
+                {
+                        config.symbols = symbols;
+                }
+                if let Some(no_ambiguous) =
+                        self.no_ambiguous.filter(|_| unset("no_ambiguous"))
+                {
+                        config.no_ambiguous = no_ambiguous;
+                }
+                if let Some(digits_min) =
+                        self.digits_min.filter(|_| unset("digits_min"))
+                {
+                        config.digits_min = digits_min;
+                }
+                if let Some(upper_min) =
+                        self.upper_min.filter(|_| unset("upper_min"))
+                {
+                        config.upper_min = upper_min;
+                }
+                Ok(())
+        }
+}
+
+fn parse<T: std::str::FromStr<Err = String>>(value: &str) -> Result<T> {
+        value.parse()
+                .map_err(|e: String| anyhow::anyhow!("In profile: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        #[test]
+        fn test_cli_options_override_profile() {
+                let profile: ConfigFile = toml::from_str(
+                        r#"
+                        [profiles.work]
+                        count = 3
+                        length = 32
+                        type = "json"
+                        source = "local"
+                        symbols = true
+                        digits-min = 2
+                        "#,
+                )
+                .unwrap();
+                let profile: Profile =
+                        profile.profiles.into_values().next().unwrap();
+
+                let args = ["password-generator", "--length", "12"];
+                let matches: ArgMatches =
+                        <Config as clap::CommandFactory>::command()
+                                .get_matches_from(args);
+                let mut config: Config =
+                        <Config as clap::FromArgMatches>::from_arg_matches(
+                                &matches,
+                        )
+                        .unwrap();
+                profile.apply(&mut config, &matches).unwrap();
+
+                assert_eq!(config.count, 3);
+                assert_eq!(config.length, 12);
+                assert_eq!(config.r#type, OutputType::Json);
+                assert_eq!(config.source, Source::Local);
+                assert!(config.symbols);
+                assert_eq!(config.digits_min, 2);
+        }
+
+        #[test]
+        fn test_unknown_keys_are_rejected() {
+                assert!(toml::from_str::<ConfigFile>(
+                        "[profiles.work]\nlenght = 3"
+                )
+                .is_err());
+        }
+}
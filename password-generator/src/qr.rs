@@ -0,0 +1,55 @@
+//! Terminal QR code rendering for passwords and Wi-Fi credentials.
+
+use anyhow::{Context, Result};
+use qrcode::render::unicode::Dense1x2;
+use qrcode::QrCode;
+
+/// Builds a `WIFI:` payload that phones join directly when scanned.
+pub fn wifi_payload(ssid: &str, password: &str) -> String {
+        format!("WIFI:T:WPA;S:{};P:{};;", escape(ssid), escape(password))
+}
+
+/// Escapes the characters the `WIFI:` format reserves.
+fn escape(value: &str) -> String {
+        let mut escaped: String = String::with_capacity(value.len());
+        for c in value.chars() {
+                if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                        escaped.push('\\');
+                }
+                escaped.push(c);
+        }
+        escaped
+}
+
+/// Renders `payload` with half-block characters, light on dark so it scans
+/// from a terminal with a dark background.
+pub fn render(payload: &str) -> Result<String> {
+        let code: QrCode = QrCode::new(payload.as_bytes())
+                .context("Payload is too long for a QR code")?;
+        Ok(code.render::<Dense1x2>()
+                .dark_color(Dense1x2::Light)
+                .light_color(Dense1x2::Dark)
+                .build())
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        #[test]
+        fn test_wifi_payload_escapes_reserved_characters() {
+                assert_eq!(
+                        wifi_payload("Guest;Net", r#"a:b,c"d\e"#),
+                        r#"WIFI:T:WPA;S:Guest\;Net;P:a\:b\,c\"d\\e;;"#
+                );
+        }
+
+        #[test]
+        fn test_render_produces_square_block() {
+                let image: String = render("password").unwrap();
+                let lines: Vec<&str> = image.lines().collect();
+                assert!(!lines.is_empty());
+                let width: usize = lines[0].chars().count();
+                assert!(lines.iter().all(|l| l.chars().count() == width));
+        }
+}
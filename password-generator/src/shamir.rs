@@ -0,0 +1,276 @@
+//! Shamir secret sharing over GF(256).
+//!
+//! Each byte of the secret is shared independently using a random
+//! polynomial of degree `threshold - 1`. Shares are rendered as
+//! `<threshold>-<x>-<hex>` so that `combine` can validate them.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use rand::{rngs::OsRng, TryRngCore};
+
+// Split specification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitSpec {
+        pub shares: u8,
+        pub threshold: u8,
+}
+
+impl FromStr for SplitSpec {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (shares, threshold) =
+                        s.split_once(':').ok_or_else(|| {
+                                format!(
+                                        "Invalid split spec: {}. Must be 'N:K'",
+                                        s
+                                )
+                        })?;
+                let shares: u8 = shares.trim().parse().map_err(|_| {
+                        format!("Invalid share count: {}", shares)
+                })?;
+                let threshold: u8 = threshold.trim().parse().map_err(|_| {
+                        format!("Invalid threshold: {}", threshold)
+                })?;
+
+                if threshold < 2 {
+                        return Err("Threshold must be at least 2".to_string());
+                }
+                if threshold > shares {
+                        return Err(format!(
+                                "Threshold {} exceeds share count {}",
+                                threshold, shares
+                        ));
+                }
+
+                Ok(Self { shares, threshold })
+        }
+}
+
+// Share
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+        pub threshold: u8,
+        pub x: u8,
+        pub y: Vec<u8>,
+}
+
+impl fmt::Display for Share {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}-{}-", self.threshold, self.x)?;
+                self.y.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+        }
+}
+
+impl FromStr for Share {
+        type Err = anyhow::Error;
+
+        fn from_str(s: &str) -> Result<Self> {
+                let mut parts = s.trim().splitn(3, '-');
+                let (Some(threshold), Some(x), Some(hex)) =
+                        (parts.next(), parts.next(), parts.next())
+                else {
+                        bail!("Invalid share: {}", s);
+                };
+
+                let threshold: u8 = threshold
+                        .parse()
+                        .with_context(|| format!("Invalid share: {}", s))?;
+                let x: u8 = x
+                        .parse()
+                        .with_context(|| format!("Invalid share: {}", s))?;
+                if threshold < 2
+                        || x == 0
+                        || !hex.is_ascii()
+                        || hex.len() % 2 != 0
+                {
+                        bail!("Invalid share: {}", s);
+                }
+
+                let y: Vec<u8> = (0..hex.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+                        .collect::<Result<_, _>>()
+                        .with_context(|| format!("Invalid share: {}", s))?;
+
+                Ok(Self { threshold, x, y })
+        }
+}
+
+// GF(256) arithmetic (AES polynomial x^8 + x^4 + x^3 + x + 1)
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product: u8 = 0;
+        while b != 0 {
+                if b & 1 != 0 {
+                        product ^= a;
+                }
+                let carry: bool = a & 0x80 != 0;
+                a <<= 1;
+                if carry {
+                        a ^= 0x1b;
+                }
+                b >>= 1;
+        }
+        product
+}
+
+fn gf_inv(a: u8) -> u8 {
+        // a^254 == a^-1 in GF(256)
+        let mut result: u8 = 1;
+        let mut base: u8 = a;
+        let mut exponent: u8 = 254;
+        while exponent != 0 {
+                if exponent & 1 != 0 {
+                        result = gf_mul(result, base);
+                }
+                base = gf_mul(base, base);
+                exponent >>= 1;
+        }
+        result
+}
+
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+        coefficients
+                .iter()
+                .rev()
+                .fold(0, |acc, &coefficient| gf_mul(acc, x) ^ coefficient)
+}
+
+// Splitting and combining
+pub fn split(secret: &[u8], spec: SplitSpec) -> Result<Vec<Share>> {
+        let mut shares: Vec<Share> = (1..=spec.shares)
+                .map(|x| Share {
+                        threshold: spec.threshold,
+                        x,
+                        y: Vec::with_capacity(secret.len()),
+                })
+                .collect();
+
+        let mut coefficients: Vec<u8> = vec![0; spec.threshold as usize];
+        for &byte in secret {
+                coefficients[0] = byte;
+                OsRng.try_fill_bytes(&mut coefficients[1..])
+                        .context("Failed to read from the OS random source")?;
+
+                for share in &mut shares {
+                        share.y.push(evaluate(&coefficients, share.x));
+                }
+        }
+
+        Ok(shares)
+}
+
+pub fn combine(shares: &[Share]) -> Result<Vec<u8>> {
+        let Some(first) = shares.first() else {
+                bail!("No shares provided");
+        };
+
+        if shares.iter().any(|share| {
+                share.threshold != first.threshold
+                        || share.y.len() != first.y.len()
+        }) {
+                bail!("Shares do not belong to the same secret");
+        }
+        for (i, share) in shares.iter().enumerate() {
+                if shares[..i].iter().any(|other| other.x == share.x) {
+                        bail!("Duplicate share index: {}", share.x);
+                }
+        }
+        if shares.len() < first.threshold as usize {
+                bail!(
+                        "Need at least {} shares, got {}",
+                        first.threshold,
+                        shares.len()
+                );
+        }
+
+        let used: &[Share] = &shares[..first.threshold as usize];
+        let secret: Vec<u8> = (0..first.y.len())
+                .map(|position| {
+                        used.iter().fold(0, |acc, share| {
+                                let basis: u8 = used
+                                        .iter()
+                                        .filter(|other| other.x != share.x)
+                                        .fold(1, |basis, other| {
+                                                gf_mul(
+                                                        basis,
+                                                        gf_mul(
+                                                                other.x,
+                                                                gf_inv(other.x
+                                                                        ^ share.x),
+                                                        ),
+                                                )
+                                        });
+                                acc ^ gf_mul(share.y[position], basis)
+                        })
+                })
+                .collect();
+
+        Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+        use super::*;
+
+        #[test]
+        fn test_split_spec_parsing() {
+                assert_eq!(
+                        "5:3".parse::<SplitSpec>().unwrap(),
+                        SplitSpec {
+                                shares: 5,
+                                threshold: 3
+                        }
+                );
+                assert!("3:5".parse::<SplitSpec>().is_err());
+                assert!("3:1".parse::<SplitSpec>().is_err());
+                assert!("3".parse::<SplitSpec>().is_err());
+        }
+
+        #[test]
+        fn test_gf_inverse() {
+                for a in 1..=255u8 {
+                        assert_eq!(gf_mul(a, gf_inv(a)), 1);
+                }
+        }
+
+        #[test]
+        fn test_split_and_combine_roundtrip() {
+                let secret: &[u8] = b"correct horse battery staple";
+                let spec: SplitSpec = "5:3".parse().unwrap();
+                let shares: Vec<Share> = split(secret, spec).unwrap();
+
+                assert_eq!(shares.len(), 5);
+                assert_eq!(combine(&shares[2..5]).unwrap(), secret);
+                assert_eq!(combine(&shares[..3]).unwrap(), secret);
+                assert!(combine(&shares[..2]).is_err());
+        }
+
+        #[test]
+        fn test_share_display_roundtrip() {
+                let share = Share {
+                        threshold: 2,
+                        x: 3,
+                        y: vec![0x00, 0xab, 0xff],
+                };
+                let text: String = share.to_string();
+
+                assert_eq!(text, "2-3-00abff");
+                assert_eq!(text.parse::<Share>().unwrap(), share);
+        }
+
+        #[test]
+        fn test_share_rejects_threshold_below_two() {
+                assert!("0-1-6869".parse::<Share>().is_err());
+                assert!("1-1-6869".parse::<Share>().is_err());
+                assert!("2-0-6869".parse::<Share>().is_err());
+        }
+
+        #[test]
+        fn test_share_rejects_non_ascii_data() {
+                assert!("2-1-aéb".parse::<Share>().is_err());
+                assert!("2-1-éé".parse::<Share>().is_err());
+        }
+}
@@ -0,0 +1,166 @@
+use serde_json::Value;
+use std::fmt;
+
+/// Field name Django uses for errors that are not tied to a single field.
+const NON_FIELD_ERRORS: &str = "__all__";
+
+/// Validation errors returned by the API, grouped by the form field they
+/// belong to. Messages are kept exactly as the server sent them.
+#[derive(Debug, Default, PartialEq)]
+pub struct ApiErrors {
+    /// Errors that do not belong to any particular field.
+    pub general: Vec<String>,
+    /// Field name and its messages, in the order the server listed them.
+    pub fields: Vec<(String, Vec<String>)>,
+}
+
+impl ApiErrors {
+    /// Parses the `errors` value of an API response. The server sends either
+    /// a plain string, a list of messages, or an object mapping field names
+    /// to one or more messages (each a string or a `{"message": ...}` object).
+    pub fn from_value(value: &Value) -> Self {
+        let mut errors = ApiErrors::default();
+        match value {
+            Value::Object(map) => {
+                for (field, messages) in map {
+                    let messages = messages_of(messages);
+                    if messages.is_empty() {
+                        continue;
+                    }
+                    if field == NON_FIELD_ERRORS {
+                        errors.general.extend(messages);
+                    } else {
+                        errors.fields.push((field.clone(), messages));
+                    }
+                }
+            }
+            other => errors.general = messages_of(other),
+        }
+        errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.general.is_empty() && self.fields.is_empty()
+    }
+
+    /// Renders every message on a single line, for places like table rows.
+    pub fn summary(&self) -> String {
+        self.general
+            .iter()
+            .cloned()
+            .chain(self.fields.iter().flat_map(|(field, messages)| {
+                messages.iter().map(move |m| format!("{}: {}", field, m))
+            }))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+fn messages_of(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => {
+            let s = s.trim();
+            if s.is_empty() {
+                vec![]
+            } else {
+                vec![s.to_string()]
+            }
+        }
+        Value::Array(items) => items.iter().flat_map(messages_of).collect(),
+        Value::Object(map) => map.get("message").map(messages_of).unwrap_or_default(),
+        Value::Null => vec![],
+        other => vec![other.to_string()],
+    }
+}
+
+impl fmt::Display for ApiErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        for message in &self.general {
+            lines.push(message.clone());
+        }
+        for (field, messages) in &self.fields {
+            lines.push(format!("{}:", field));
+            for message in messages {
+                lines.push(format!("  {}", message));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors_of(response: &str) -> ApiErrors {
+        let response: Value = serde_json::from_str(response).unwrap();
+        ApiErrors::from_value(&response["errors"])
+    }
+
+    #[test]
+    fn keeps_string_errors_whole() {
+        let errors = errors_of(
+            r#"{"status": "400", "content": "Invalid data",
+                "errors": "Entry with this url already exists. See rentry.co/how for details."}"#,
+        );
+        assert_eq!(
+            errors.general,
+            vec!["Entry with this url already exists. See rentry.co/how for details."]
+        );
+        assert!(errors.fields.is_empty());
+    }
+
+    #[test]
+    fn groups_field_errors() {
+        let errors = errors_of(
+            r#"{"status": "400", "content": "Invalid data", "errors": {
+                "url": ["Entry with this url already exists."],
+                "edit_code": ["Ensure this value has at most 100 characters (it has 120).",
+                              "Invalid characters."],
+                "__all__": ["Please try again."]}}"#,
+        );
+        assert_eq!(errors.general, vec!["Please try again."]);
+        assert_eq!(
+            errors.fields,
+            vec![
+                (
+                    "url".to_string(),
+                    vec!["Entry with this url already exists.".to_string()]
+                ),
+                (
+                    "edit_code".to_string(),
+                    vec![
+                        "Ensure this value has at most 100 characters (it has 120).".to_string(),
+                        "Invalid characters.".to_string(),
+                    ]
+                ),
+            ]
+        );
+        assert_eq!(
+            errors.to_string(),
+            "Please try again.\n\
+             url:\n  Entry with this url already exists.\n\
+             edit_code:\n  Ensure this value has at most 100 characters (it has 120).\n  Invalid characters."
+        );
+    }
+
+    #[test]
+    fn reads_message_objects() {
+        let errors = errors_of(
+            r#"{"status": "400", "content": "Invalid data", "errors": {
+                "text": [{"message": "This field is required.", "code": "required"}]}}"#,
+        );
+        assert_eq!(
+            errors.summary(),
+            "text: This field is required.".to_string()
+        );
+    }
+
+    #[test]
+    fn missing_or_empty_errors_are_empty() {
+        assert!(errors_of(r#"{"status": "403", "content": "Forbidden"}"#).is_empty());
+        assert!(errors_of(r#"{"status": "400", "errors": ""}"#).is_empty());
+        assert!(errors_of(r#"{"status": "400", "errors": {"url": []}}"#).is_empty());
+    }
+}
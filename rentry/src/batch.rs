@@ -0,0 +1,101 @@
+use crate::{Entry, RentryClient, RentryError};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+pub const DEFAULT_JOBS: usize = 4;
+
+pub struct Published {
+    pub file: PathBuf,
+    pub result: Result<Entry, RentryError>,
+}
+
+/// Publishes every file as its own entry, with at most `jobs` requests in
+/// flight. The CSRF token is fetched once up front and shared by all uploads.
+/// Results are returned in the same order as `files`.
+pub async fn publish_files(
+    client: &mut RentryClient,
+    files: Vec<PathBuf>,
+    edit_code: Option<String>,
+    jobs: usize,
+) -> Result<Vec<Published>, RentryError> {
+    client.get_csrf_token().await?;
+
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, file) in files.iter().enumerate() {
+        let mut client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let file = file.clone();
+        let edit_code = edit_code.clone().unwrap_or_default();
+
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            let result = match tokio::fs::read_to_string(&file).await {
+                Ok(text) => {
+                    client
+                        .create_entry(Entry {
+                            url: String::new(),
+                            edit_code,
+                            text: text.trim().to_string(),
+                        })
+                        .await
+                }
+                Err(e) => Err(RentryError::Io(e)),
+            };
+            (index, Published { file, result })
+        });
+    }
+
+    let mut published: Vec<Option<Published>> = files.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = joined.expect("upload task panicked");
+        published[index] = Some(result);
+    }
+
+    Ok(published.into_iter().flatten().collect())
+}
+
+pub fn print_table(published: &[Published]) {
+    let width = published
+        .iter()
+        .map(|p| p.file.display().to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max("File".len());
+
+    println!(
+        "{:<width$}  {:<30}  Edit code",
+        "File",
+        "Url",
+        width = width
+    );
+    for p in published {
+        match &p.result {
+            Ok(entry) => println!(
+                "{:<width$}  {:<30}  {}",
+                p.file.display().to_string(),
+                entry.url,
+                entry.edit_code,
+                width = width
+            ),
+            Err(e) => {
+                let mut message = e.to_string();
+                if let RentryError::Api(_, errors) = e
+                    && !errors.is_empty()
+                {
+                    message.push_str("; ");
+                    message.push_str(&errors.summary());
+                }
+                println!(
+                    "{:<width$}  failed: {}",
+                    p.file.display().to_string(),
+                    message,
+                    width = width
+                );
+            }
+        }
+    }
+}
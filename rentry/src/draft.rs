@@ -0,0 +1,140 @@
+use crate::{Entry, RentryError};
+use serde_json::{Value, json};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DRAFTS_DIR: &str = "rentry/drafts";
+
+pub struct Draft {
+    pub id: String,
+    pub entry: Entry,
+}
+
+fn drafts_dir() -> PathBuf {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    data_home.join(DRAFTS_DIR)
+}
+
+pub fn save(entry: &Entry) -> Result<Draft, RentryError> {
+    let dir = drafts_dir();
+    fs::create_dir_all(&dir)?;
+
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let value = json!({
+        "url": entry.url,
+        "edit_code": entry.edit_code,
+        "text": entry.text,
+    });
+
+    // Drafts queued within the same millisecond get a numeric suffix instead
+    // of overwriting each other
+    for attempt in 0u32.. {
+        let id = if attempt == 0 {
+            millis.to_string()
+        } else {
+            format!("{}-{}", millis, attempt)
+        };
+        // Drafts hold edit codes, so keep them private to the user
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = match options.open(dir.join(format!("{}.json", id))) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e.into()),
+        };
+        file.write_all(value.to_string().as_bytes())?;
+
+        return Ok(Draft {
+            id,
+            entry: entry.clone(),
+        });
+    }
+
+    unreachable!("draft id suffixes exhausted")
+}
+
+pub fn load_all() -> Result<Vec<Draft>, RentryError> {
+    let dir = drafts_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut drafts = Vec::new();
+    for file in fs::read_dir(&dir)? {
+        let path = file?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+
+        let value: Value = serde_json::from_str(&fs::read_to_string(&path)?).map_err(|e| {
+            RentryError::Validation(format!("Invalid draft {}: {}", path.display(), e))
+        })?;
+        let field = |name: &str| value[name].as_str().unwrap_or_default().to_string();
+
+        drafts.push(Draft {
+            id: id.to_string(),
+            entry: Entry {
+                url: field("url"),
+                edit_code: field("edit_code"),
+                text: field("text"),
+            },
+        });
+    }
+
+    drafts.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(drafts)
+}
+
+/// Whether `id` has the `<millis>` or `<millis>-<n>` shape `save` gives
+/// out, so it can't name a file outside the drafts directory
+fn is_valid_id(id: &str) -> bool {
+    let (millis, suffix) = id.split_once('-').unwrap_or((id, "0"));
+    [millis, suffix]
+        .iter()
+        .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+pub fn remove(id: &str) -> Result<(), RentryError> {
+    if !is_valid_id(id) {
+        return Err(RentryError::Validation(format!("Invalid draft id {}", id)));
+    }
+    let path = drafts_dir().join(format!("{}.json", id));
+    if !path.exists() {
+        return Err(RentryError::Validation(format!("No draft with id {}", id)));
+    }
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_generated_ids() {
+        assert!(is_valid_id("1718000000000"));
+        assert!(is_valid_id("1718000000000-2"));
+    }
+
+    #[test]
+    fn rejects_paths_as_ids() {
+        for id in [
+            "", "../x", "..", "a/b", "1/2", "123-", "-1", "1-2-3", "12.json",
+        ] {
+            assert!(!is_valid_id(id), "{:?}", id);
+        }
+    }
+}
@@ -1,11 +1,21 @@
+mod api_error;
+mod batch;
+mod draft;
+mod log;
+mod patch;
+
+use api_error::ApiErrors;
 use clap::{Parser, Subcommand};
+use log::{Level, trace, verbose};
+use patch::Patch;
 use reqwest::{
-    Client,
-    header::{ACCEPT_ENCODING, HeaderMap, HeaderValue, REFERER, USER_AGENT},
+    Client, StatusCode,
+    header::{ACCEPT_ENCODING, HeaderMap, HeaderValue, REFERER, RETRY_AFTER, USER_AGENT},
 };
 use serde_json::Value;
 use std::collections::HashMap;
 use std::io::{self, Read};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use thiserror::Error;
 use url::Url;
@@ -13,15 +23,23 @@ use url::Url;
 const BASE_PROTOCOL: &str = "https://";
 const BASE_URL: &str = "rentry.co";
 const SUCCESS_STATUS: &str = "200";
+/// Longest wait between attempts, whether from backoff or Retry-After
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(300);
 
 #[derive(Error, Debug)]
 enum RentryError {
     #[error("Validation error: {0}")]
     Validation(String),
     #[error("API error: {0}")]
-    Api(String, Vec<String>),
+    Api(String, ApiErrors),
     #[error("Request error: {0}")]
     Request(#[from] reqwest::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Rate limited by the server")]
+    RateLimited(Option<Duration>),
+    #[error("CSRF token rejected by the server")]
+    CsrfRejected,
 }
 
 #[derive(Clone)]
@@ -62,7 +80,7 @@ impl UrllibClient {
             .timeout(Duration::from_secs(timeout))
             .default_headers(headers)
             .build()
-            .map_err(|e| RentryError::Request(e))?;
+            .map_err(RentryError::Request)?;
 
         Ok(UrllibClient {
             client,
@@ -80,7 +98,7 @@ impl UrllibClient {
         if let Some(h) = headers {
             request = request.headers(h);
         }
-        Ok(request.send().await?)
+        check_rate_limit(request.send().await?)
     }
 
     async fn post(
@@ -93,39 +111,78 @@ impl UrllibClient {
         if let Some(h) = headers {
             request = request.headers(h);
         }
-        Ok(request.send().await?)
+        check_rate_limit(request.send().await?)
+    }
+}
+
+/// Whether the server refused a form because of its CSRF token. Django
+/// answers those with a 403 whose body mentions CSRF, as HTML or JSON.
+fn is_csrf_rejection(status: StatusCode, body: &[u8]) -> bool {
+    let forbidden = status == StatusCode::FORBIDDEN
+        || serde_json::from_slice::<Value>(body).is_ok_and(|v| v["status"] == "403");
+    forbidden
+        && String::from_utf8_lossy(body)
+            .to_ascii_lowercase()
+            .contains("csrf")
+}
+
+/// Turns a 429 response into `RateLimited`, carrying how long the server
+/// asked us to wait. Retry-After may be a number of seconds or an HTTP date.
+fn check_rate_limit(response: reqwest::Response) -> Result<reqwest::Response, RentryError> {
+    if response.status() != StatusCode::TOO_MANY_REQUESTS {
+        return Ok(response);
     }
+
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.trim()
+                .parse::<u64>()
+                .map(Duration::from_secs)
+                .ok()
+                .or_else(|| {
+                    httpdate::parse_http_date(v)
+                        .ok()
+                        .map(|at| at.duration_since(SystemTime::now()).unwrap_or_default())
+                })
+        });
+    verbose!("Rate limited, Retry-After: {:?}", retry_after);
+    Err(RentryError::RateLimited(retry_after))
 }
 
+#[derive(Clone)]
 struct RentryClient {
     client: UrllibClient,
     csrf_token_ttl: u64,
     max_retries: u32,
+    retry_delay: Duration,
 }
 
 impl RentryClient {
-    fn new(max_retries: u32) -> Result<Self, RentryError> {
+    fn new(max_retries: u32, retry_delay: Duration) -> Result<Self, RentryError> {
         Ok(RentryClient {
             client: UrllibClient::new(30)?,
             max_retries,
+            retry_delay,
             csrf_token_ttl: 3600,
         })
     }
 
     async fn get_csrf_token(&mut self) -> Result<String, RentryError> {
         let current_time = SystemTime::now();
-        if let (Some(token), Some(time)) = (&self.client.csrf_token, self.client.csrf_token_time) {
-            if current_time.duration_since(time).unwrap().as_secs() < self.csrf_token_ttl {
-                return Ok(token.clone());
-            }
+        if let (Some(token), Some(time)) = (&self.client.csrf_token, self.client.csrf_token_time)
+            && current_time.duration_since(time).unwrap().as_secs() < self.csrf_token_ttl
+        {
+            return Ok(token.clone());
         }
 
         let url = format!("{}{}", BASE_PROTOCOL, BASE_URL);
         let response = self.client.get(&url, None).await?;
 
-        // Print response status and headers for debugging
-        eprintln!("CSRF token request status: {}", response.status());
-        eprintln!("CSRF token request headers: {:#?}", response.headers());
+        verbose!("CSRF token request status: {}", response.status());
+        trace!("CSRF token request headers: {:#?}", response.headers());
 
         let cookies = response.headers().get_all("set-cookie");
         let token = cookies
@@ -137,13 +194,20 @@ impl RentryClient {
                         .map(|p| p.replace("csrftoken=", ""))
                 })
             })
-            .ok_or_else(|| RentryError::Api("Failed to get CSRF token".into(), vec![]))?;
+            .ok_or_else(|| {
+                RentryError::Api("Failed to get CSRF token".into(), ApiErrors::default())
+            })?;
 
         self.client.csrf_token = Some(token.clone());
         self.client.csrf_token_time = Some(current_time);
         Ok(token)
     }
 
+    fn invalidate_csrf_token(&mut self) {
+        self.client.csrf_token = None;
+        self.client.csrf_token_time = None;
+    }
+
     async fn retry<F, Fut, T>(&self, f: F) -> Result<T, RentryError>
     where
         F: Fn() -> Fut,
@@ -153,10 +217,22 @@ impl RentryClient {
         for attempt in 0..self.max_retries {
             match f().await {
                 Ok(response) => return Ok(response),
+                // Resending a rejected token cannot succeed
+                Err(RentryError::CsrfRejected) => return Err(RentryError::CsrfRejected),
                 Err(e) => {
+                    // Honor the server's Retry-After when rate limited,
+                    // otherwise back off exponentially from retry_delay
+                    let delay = match e {
+                        RentryError::RateLimited(Some(delay)) => delay,
+                        _ => self
+                            .retry_delay
+                            .saturating_mul(2u32.saturating_pow(attempt)),
+                    }
+                    .min(MAX_RETRY_DELAY);
                     last_error = Some(e);
                     if attempt < self.max_retries - 1 {
-                        tokio::time::sleep(Duration::from_secs(2u64.pow(attempt))).await;
+                        verbose!("Retrying in {:?}", delay);
+                        tokio::time::sleep(delay).await;
                     }
                 }
             }
@@ -164,6 +240,63 @@ impl RentryClient {
         Err(last_error.unwrap())
     }
 
+    /// Posts a form with the cached CSRF token. If the server rejects the
+    /// token, it is dropped and refetched, and the request is sent once more.
+    async fn post_form(
+        &mut self,
+        label: &str,
+        url: &str,
+        mut payload: HashMap<&str, String>,
+        headers: HeaderMap,
+    ) -> Result<Value, RentryError> {
+        let mut refreshed = false;
+        loop {
+            payload.insert("csrfmiddlewaretoken", self.get_csrf_token().await?);
+            let client = self.client.clone();
+
+            let result = self
+                .retry(|| async {
+                    let resp = client
+                        .post(url, payload.clone(), Some(headers.clone()))
+                        .await?;
+
+                    let status = resp.status();
+                    verbose!("{} request status: {}", label, status);
+                    trace!("{} request headers: {:#?}", label, resp.headers());
+
+                    // Get response body as bytes
+                    let bytes = resp.bytes().await?;
+                    trace!(
+                        "{} response body: {}",
+                        label,
+                        String::from_utf8_lossy(&bytes)
+                    );
+
+                    if is_csrf_rejection(status, &bytes) {
+                        return Err(RentryError::CsrfRejected);
+                    }
+
+                    // Try to parse the JSON
+                    serde_json::from_slice::<Value>(&bytes).map_err(|e| {
+                        RentryError::Api(
+                            format!("Failed to parse JSON response: {}", e),
+                            ApiErrors::default(),
+                        )
+                    })
+                })
+                .await;
+
+            match result {
+                Err(RentryError::CsrfRejected) if !refreshed => {
+                    verbose!("CSRF token rejected, fetching a new one");
+                    self.invalidate_csrf_token();
+                    refreshed = true;
+                }
+                result => return result,
+            }
+        }
+    }
+
     async fn get_raw(&mut self, url: &str) -> Result<String, RentryError> {
         if url.is_empty() {
             return Err(RentryError::Validation("URL is required".into()));
@@ -183,18 +316,19 @@ impl RentryClient {
             .retry(|| async {
                 let resp = client.get(&endpoint, Some(headers.clone())).await?;
 
-                // Print response status and headers for debugging
-                eprintln!("Get raw request status: {}", resp.status());
-                eprintln!("Get raw request headers: {:#?}", resp.headers());
+                verbose!("Get raw request status: {}", resp.status());
+                trace!("Get raw request headers: {:#?}", resp.headers());
 
                 // Get response body as bytes
                 let bytes = resp.bytes().await?;
-                let text = String::from_utf8_lossy(&bytes);
-                eprintln!("Get raw response body: {}", text);
+                trace!("Get raw response body: {}", String::from_utf8_lossy(&bytes));
 
                 // Try to parse the JSON
                 serde_json::from_slice::<Value>(&bytes).map_err(|e| {
-                    RentryError::Api(format!("Failed to parse JSON response: {}", e), vec![])
+                    RentryError::Api(
+                        format!("Failed to parse JSON response: {}", e),
+                        ApiErrors::default(),
+                    )
                 })
             })
             .await?;
@@ -202,7 +336,7 @@ impl RentryClient {
         if response["status"] != SUCCESS_STATUS {
             return Err(RentryError::Api(
                 format!("Failed to get raw content: {}", response["content"]),
-                vec![],
+                ApiErrors::default(),
             ));
         }
 
@@ -214,9 +348,7 @@ impl RentryClient {
             return Err(RentryError::Validation("Text is required".into()));
         }
 
-        let csrftoken = self.get_csrf_token().await?;
         let mut payload = HashMap::new();
-        payload.insert("csrfmiddlewaretoken", csrftoken);
         payload.insert("url", entry.url.clone());
         payload.insert("edit_code", entry.edit_code.clone());
         payload.insert("text", entry.text.clone());
@@ -237,39 +369,13 @@ impl RentryClient {
             HeaderValue::from_static("XMLHttpRequest"),
         );
 
-        let client = self.client.clone();
         let url = format!("{}{}/api/new", BASE_PROTOCOL, BASE_URL);
-
         let response = self
-            .retry(|| async {
-                let resp = client
-                    .post(&url, payload.clone(), Some(headers.clone()))
-                    .await?;
-
-                // Print response status and headers for debugging
-                eprintln!("Create entry request status: {}", resp.status());
-                eprintln!("Create entry request headers: {:#?}", resp.headers());
-
-                // Get response body as bytes
-                let bytes = resp.bytes().await?;
-                let text = String::from_utf8_lossy(&bytes);
-                eprintln!("Create entry response body: {}", text);
-
-                // Try to parse the JSON
-                serde_json::from_slice::<Value>(&bytes).map_err(|e| {
-                    RentryError::Api(format!("Failed to parse JSON response: {}", e), vec![])
-                })
-            })
+            .post_form("Create entry", &url, payload, headers)
             .await?;
 
         if response["status"] != SUCCESS_STATUS {
-            let errors = response["errors"]
-                .as_str()
-                .unwrap_or("")
-                .split('.')
-                .filter(|e| !e.is_empty())
-                .map(String::from)
-                .collect();
+            let errors = ApiErrors::from_value(&response["errors"]);
             return Err(RentryError::Api(
                 format!("Failed to create entry: {}", response["content"]),
                 errors,
@@ -294,9 +400,7 @@ impl RentryClient {
             return Err(RentryError::Validation("Text is required".into()));
         }
 
-        let csrftoken = self.get_csrf_token().await?;
         let mut payload = HashMap::new();
-        payload.insert("csrfmiddlewaretoken", csrftoken);
         payload.insert("edit_code", entry.edit_code.clone());
         payload.insert("text", entry.text.clone());
 
@@ -312,39 +416,11 @@ impl RentryClient {
         );
         headers.insert(reqwest::header::ORIGIN, HeaderValue::from_static(BASE_URL));
 
-        let client = self.client.clone();
         let url = format!("{}{}/api/edit/{}", BASE_PROTOCOL, BASE_URL, entry.url);
-
-        let response = self
-            .retry(|| async {
-                let resp = client
-                    .post(&url, payload.clone(), Some(headers.clone()))
-                    .await?;
-
-                // Print response status and headers for debugging
-                eprintln!("Edit entry request status: {}", resp.status());
-                eprintln!("Edit entry request headers: {:#?}", resp.headers());
-
-                // Get response body as bytes
-                let bytes = resp.bytes().await?;
-                let text = String::from_utf8_lossy(&bytes);
-                eprintln!("Edit entry response body: {}", text);
-
-                // Try to parse the JSON
-                serde_json::from_slice::<Value>(&bytes).map_err(|e| {
-                    RentryError::Api(format!("Failed to parse JSON response: {}", e), vec![])
-                })
-            })
-            .await?;
+        let response = self.post_form("Edit entry", &url, payload, headers).await?;
 
         if response["status"] != SUCCESS_STATUS {
-            let errors = response["errors"]
-                .as_str()
-                .unwrap_or("")
-                .split('.')
-                .filter(|e| !e.is_empty())
-                .map(String::from)
-                .collect();
+            let errors = ApiErrors::from_value(&response["errors"]);
             return Err(RentryError::Api(
                 format!("Failed to edit entry: {}", response["content"]),
                 errors,
@@ -357,13 +433,33 @@ impl RentryClient {
 
 #[derive(Subcommand)]
 enum Command {
-    #[clap(about = "Create a new entry")]
+    #[clap(about = "Create a new entry, or one entry per file")]
     New {
         #[clap(short, long)]
         url: Option<String>,
         #[clap(short = 'p', long = "edit-code")]
         edit_code: Option<String>,
-        text: Option<String>,
+        #[clap(
+            short,
+            long = "file",
+            help = "Publish a file as its own entry (repeatable, same as passing it positionally)"
+        )]
+        files: Vec<PathBuf>,
+        #[clap(short, long, default_value_t = batch::DEFAULT_JOBS, help = "Maximum number of files published at once")]
+        jobs: usize,
+        #[clap(long, help = "Copy the created url to the clipboard")]
+        copy: bool,
+        #[clap(
+            long,
+            conflicts_with = "files",
+            help = "Echo the entry text to stdout while publishing"
+        )]
+        tee: bool,
+        #[clap(
+            value_name = "TEXT|FILES",
+            help = "Entry text, or files to publish as separate entries; text is read from stdin when omitted"
+        )]
+        inputs: Vec<String>,
     },
     #[clap(about = "Edit an existing entry")]
     Edit {
@@ -371,6 +467,24 @@ enum Command {
         url: String,
         #[clap(short = 'p', long = "edit-code")]
         edit_code: String,
+        #[clap(
+            long,
+            conflicts_with_all = ["prepend", "replace_section"],
+            help = "Add the text after the current content"
+        )]
+        append: bool,
+        #[clap(
+            long,
+            conflicts_with = "replace_section",
+            help = "Add the text before the current content"
+        )]
+        prepend: bool,
+        #[clap(
+            long = "replace-section",
+            value_name = "HEADING",
+            help = "Replace the body of the markdown section with this heading"
+        )]
+        replace_section: Option<String>,
         text: Option<String>,
     },
     #[clap(about = "Get the raw content of an entry")]
@@ -378,6 +492,29 @@ enum Command {
         #[clap(short, long)]
         url: String,
     },
+    #[clap(about = "Manage entries queued for publishing later")]
+    Draft {
+        #[clap(subcommand)]
+        action: DraftCommand,
+    },
+    #[clap(about = "Publish all queued drafts")]
+    Sync,
+}
+
+#[derive(Subcommand)]
+enum DraftCommand {
+    #[clap(about = "Queue a new entry without publishing it")]
+    Add {
+        #[clap(short, long)]
+        url: Option<String>,
+        #[clap(short = 'p', long = "edit-code")]
+        edit_code: Option<String>,
+        text: Option<String>,
+    },
+    #[clap(about = "List queued drafts")]
+    List,
+    #[clap(about = "Remove a queued draft")]
+    Remove { id: String },
 }
 
 #[derive(Parser)]
@@ -385,19 +522,115 @@ enum Command {
 struct Args {
     #[clap(subcommand)]
     command: Command,
+    #[clap(short, long, global = true, help = "Print request status to stderr")]
+    verbose: bool,
+    #[clap(
+        long,
+        global = true,
+        help = "Print request status, headers and response bodies to stderr"
+    )]
+    trace: bool,
+    #[clap(
+        long,
+        global = true,
+        default_value_t = 3,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Number of attempts per request"
+    )]
+    max_retries: u32,
+    #[clap(
+        long,
+        global = true,
+        default_value_t = 1,
+        value_name = "SECONDS",
+        help = "Delay before the first retry, doubled on each further attempt up to 5 minutes"
+    )]
+    retry_delay: u64,
+}
+
+/// Turns the url returned by the API into a full link, leaving it untouched
+/// when it is already absolute.
+fn entry_url(url: &str) -> String {
+    if Url::parse(url).is_ok() {
+        url.to_string()
+    } else {
+        format!(
+            "{}{}/{}",
+            BASE_PROTOCOL,
+            BASE_URL,
+            url.trim_start_matches('/')
+        )
+    }
+}
+
+/// Sorts the positional arguments of `new`: several arguments, or a single
+/// one naming an existing file, are files to publish; anything else is the
+/// entry text
+fn new_inputs(mut inputs: Vec<String>, mut files: Vec<PathBuf>) -> (Option<String>, Vec<PathBuf>) {
+    if inputs.len() == 1 && !Path::new(&inputs[0]).is_file() {
+        return (inputs.pop(), files);
+    }
+    files.extend(inputs.into_iter().map(PathBuf::from));
+    (None, files)
+}
+
+/// Copies `text` to the clipboard. The entry is already published by the
+/// time this runs, so a missing clipboard only warns instead of failing.
+fn copy_to_clipboard(text: &str) {
+    if let Err(e) = arboard::Clipboard::new().and_then(|mut c| c.set_text(text)) {
+        eprintln!("Failed to copy to clipboard: {}", e);
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
-    let mut client = RentryClient::new(3)?;
+    log::set_level(if args.trace {
+        Level::Trace
+    } else if args.verbose {
+        Level::Verbose
+    } else {
+        Level::Quiet
+    });
+    let mut client = RentryClient::new(args.max_retries, Duration::from_secs(args.retry_delay))?;
 
     match args.command {
         Command::New {
             url,
             edit_code,
-            text,
+            files,
+            jobs,
+            copy,
+            tee,
+            inputs,
         } => {
+            let (text, files) = new_inputs(inputs, files);
+            if !files.is_empty() {
+                if url.is_some() || text.is_some() || tee {
+                    eprintln!("Files cannot be combined with --url, --tee or entry text");
+                    std::process::exit(1);
+                }
+                if let Some(missing) = files.iter().find(|f| !f.is_file()) {
+                    eprintln!("No such file: {}", missing.display());
+                    std::process::exit(1);
+                }
+
+                let published = batch::publish_files(&mut client, files, edit_code, jobs).await?;
+                batch::print_table(&published);
+                if copy {
+                    let urls: Vec<String> = published
+                        .iter()
+                        .filter_map(|p| p.result.as_ref().ok())
+                        .map(|entry| entry_url(&entry.url))
+                        .collect();
+                    copy_to_clipboard(&urls.join("\n"));
+                }
+                if published.iter().any(|p| p.result.is_err()) {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+
             let text = text.unwrap_or_else(|| {
                 let mut input = String::new();
                 io::stdin().read_to_string(&mut input).unwrap();
@@ -409,6 +642,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 std::process::exit(1);
             }
 
+            if tee {
+                println!("{}", text);
+            }
+
             let entry = Entry {
                 url: url.unwrap_or_default(),
                 edit_code: edit_code.unwrap_or_default(),
@@ -416,13 +653,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             };
 
             match client.create_entry(entry).await {
-                Ok(result) => println!("Url: {}\nEdit code: {}", result.url, result.edit_code),
+                Ok(result) => {
+                    println!("Url: {}\nEdit code: {}", result.url, result.edit_code);
+                    if copy {
+                        copy_to_clipboard(&entry_url(&result.url));
+                    }
+                }
                 Err(e) => {
                     eprintln!("{}", e);
-                    if let RentryError::Api(_, errors) = &e {
-                        for error in errors {
-                            eprintln!("{}", error);
-                        }
+                    if let RentryError::Api(_, errors) = &e
+                        && !errors.is_empty()
+                    {
+                        eprintln!("{}", errors);
                     }
                     std::process::exit(1);
                 }
@@ -431,6 +673,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Command::Edit {
             url,
             edit_code,
+            append,
+            prepend,
+            replace_section,
             text,
         } => {
             let text = text.unwrap_or_else(|| {
@@ -444,10 +689,42 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 std::process::exit(1);
             }
 
+            let url = Url::parse(&url).map_or(url.clone(), |u| {
+                u.path().trim_start_matches('/').to_string()
+            });
+
+            let patch = if append {
+                Some(Patch::Append)
+            } else if prepend {
+                Some(Patch::Prepend)
+            } else {
+                replace_section.map(Patch::ReplaceSection)
+            };
+
+            let text = match patch {
+                Some(patch) => {
+                    let current = match client.get_raw(&url).await {
+                        Ok(content) => content,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            std::process::exit(1);
+                        }
+                    };
+                    match patch::apply(&current, &text, &patch) {
+                        Some(merged) => merged,
+                        None => {
+                            if let Patch::ReplaceSection(heading) = &patch {
+                                eprintln!("No section with heading: {}", heading);
+                            }
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                None => text,
+            };
+
             let entry = Entry {
-                url: Url::parse(&url).map_or(url.clone(), |u| {
-                    u.path().trim_start_matches('/').to_string()
-                }),
+                url,
                 edit_code,
                 text,
             };
@@ -456,10 +733,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(_) => println!("Ok"),
                 Err(e) => {
                     eprintln!("{}", e);
-                    if let RentryError::Api(_, errors) = &e {
-                        for error in errors {
-                            eprintln!("{}", error);
-                        }
+                    if let RentryError::Api(_, errors) = &e
+                        && !errors.is_empty()
+                    {
+                        eprintln!("{}", errors);
                     }
                     std::process::exit(1);
                 }
@@ -473,15 +750,106 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Ok(content) => println!("{}", content),
                 Err(e) => {
                     eprintln!("{}", e);
-                    if let RentryError::Api(_, errors) = &e {
-                        for error in errors {
-                            eprintln!("{}", error);
-                        }
+                    if let RentryError::Api(_, errors) = &e
+                        && !errors.is_empty()
+                    {
+                        eprintln!("{}", errors);
                     }
                     std::process::exit(1);
                 }
             }
         }
+        Command::Draft { action } => match action {
+            DraftCommand::Add {
+                url,
+                edit_code,
+                text,
+            } => {
+                let text = text.unwrap_or_else(|| {
+                    let mut input = String::new();
+                    io::stdin().read_to_string(&mut input).unwrap();
+                    input.trim().to_string()
+                });
+
+                if text.is_empty() {
+                    eprintln!("No text provided");
+                    std::process::exit(1);
+                }
+
+                let entry = Entry {
+                    url: url.unwrap_or_default(),
+                    edit_code: edit_code.unwrap_or_default(),
+                    text,
+                };
+                let draft = draft::save(&entry)?;
+                println!("Queued draft: {}", draft.id);
+            }
+            DraftCommand::List => {
+                for draft in draft::load_all()? {
+                    let preview: String = draft
+                        .entry
+                        .text
+                        .lines()
+                        .next()
+                        .unwrap_or("")
+                        .chars()
+                        .take(50)
+                        .collect();
+                    let url = if draft.entry.url.is_empty() {
+                        "(random url)"
+                    } else {
+                        &draft.entry.url
+                    };
+                    println!("{}\t{}\t{}", draft.id, url, preview);
+                }
+            }
+            DraftCommand::Remove { id } => {
+                draft::remove(&id)?;
+                println!("Removed draft: {}", id);
+            }
+        },
+        Command::Sync => {
+            let drafts = draft::load_all()?;
+            if drafts.is_empty() {
+                println!("No drafts to sync");
+                return Ok(());
+            }
+
+            let mut failed = 0;
+            for draft in drafts {
+                match client.create_entry(draft.entry).await {
+                    Ok(result) => {
+                        // Print first so the edit code is never lost, even if
+                        // the draft file cannot be removed afterwards
+                        println!(
+                            "{}: Url: {} Edit code: {}",
+                            draft.id, result.url, result.edit_code
+                        );
+                        if let Err(e) = draft::remove(&draft.id) {
+                            failed += 1;
+                            eprintln!(
+                                "{}: published, but failed to remove the draft: {}",
+                                draft.id, e
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        eprintln!("{}: {}", draft.id, e);
+                        if let RentryError::Api(_, errors) = &e
+                            && !errors.is_empty()
+                        {
+                            eprintln!("{}", errors);
+                        }
+                    }
+                }
+            }
+
+            if failed > 0 {
+                eprintln!("{} draft(s) failed to sync or remain queued", failed);
+                std::process::exit(1);
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,109 @@
+/// How new text is merged into the content already published.
+pub enum Patch {
+    Append,
+    Prepend,
+    /// Replace the body of the markdown section with this heading.
+    ReplaceSection(String),
+}
+
+/// Applies `patch` to `current`, returning the merged content or `None` when
+/// the section to replace does not exist.
+pub fn apply(current: &str, text: &str, patch: &Patch) -> Option<String> {
+    match patch {
+        Patch::Append => Some(join(current, text)),
+        Patch::Prepend => Some(join(text, current)),
+        Patch::ReplaceSection(heading) => replace_section(current, heading, text),
+    }
+}
+
+fn join(first: &str, second: &str) -> String {
+    let first = first.trim_end_matches('\n');
+    if first.is_empty() {
+        second.to_string()
+    } else if second.is_empty() {
+        first.to_string()
+    } else {
+        format!("{}\n{}", first, second)
+    }
+}
+
+/// Returns the level and title of a markdown ATX heading (`## Title`).
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// Replaces everything between the heading titled `title` and the next
+/// heading of the same or a higher level. The heading line itself is kept.
+fn replace_section(current: &str, title: &str, text: &str) -> Option<String> {
+    let title = heading(title).map_or(title.trim(), |(_, t)| t);
+    let lines: Vec<&str> = current.lines().collect();
+
+    let start = lines
+        .iter()
+        .position(|line| heading(line).is_some_and(|(_, t)| t.eq_ignore_ascii_case(title)))?;
+    let (level, _) = heading(lines[start])?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| heading(line).is_some_and(|(l, _)| l <= level))
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    let mut merged: Vec<&str> = lines[..=start].to_vec();
+    let text = text.trim_matches('\n');
+    if !text.is_empty() {
+        merged.push("");
+        merged.extend(text.lines());
+    }
+    if end < lines.len() {
+        merged.push("");
+        merged.extend(&lines[end..]);
+    }
+    Some(merged.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str =
+        "# Status\n\nAll good\n\n## Changelog\n\n- v1\n\n### Notes\n\nold\n\n## Contact\n\nme";
+
+    #[test]
+    fn appends_and_prepends_on_their_own_line() {
+        assert_eq!(apply("a\n", "b", &Patch::Append).unwrap(), "a\nb");
+        assert_eq!(apply("a", "b", &Patch::Prepend).unwrap(), "b\na");
+        assert_eq!(apply("", "b", &Patch::Append).unwrap(), "b");
+    }
+
+    #[test]
+    fn replaces_section_including_subsections() {
+        let patched = apply(
+            PAGE,
+            "- v2\n- v1",
+            &Patch::ReplaceSection("Changelog".into()),
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            "# Status\n\nAll good\n\n## Changelog\n\n- v2\n- v1\n\n## Contact\n\nme"
+        );
+    }
+
+    #[test]
+    fn replaces_last_section_and_accepts_heading_markers() {
+        let patched = apply(PAGE, "you", &Patch::ReplaceSection("## contact".into())).unwrap();
+        assert!(patched.ends_with("## Contact\n\nyou"));
+    }
+
+    #[test]
+    fn missing_section_is_none() {
+        assert!(apply(PAGE, "x", &Patch::ReplaceSection("Roadmap".into())).is_none());
+    }
+}
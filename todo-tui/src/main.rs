@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
     execute,
@@ -13,9 +13,14 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 use sqlx::{Row, Sqlite, migrate::MigrateDatabase, query, sqlite::SqlitePool};
-use std::fs::create_dir_all;
-use std::io;
-use std::path::PathBuf;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const STATE_FILE: &str = "ui_state";
+const BACKUP_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "todo-";
 
 #[derive(Parser)]
 #[command(name = "todo-cli")]
@@ -28,7 +33,7 @@ struct Args {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Add a new task to the todo list
+    /// Add a new task to the todo list, or one task per line of stdin with "-"
     Add { task: String },
     /// List all tasks in the todo list
     List,
@@ -38,6 +43,37 @@ enum Commands {
     Complete { id: u32 },
     /// Reset all tasks
     Reset,
+    /// Start a focus countdown for a task and log the time spent on it
+    Focus {
+        id: u32,
+        /// Length of the focus session in minutes (at most one day)
+        #[arg(short, long, default_value_t = 25, value_parser = clap::value_parser!(u64).range(1..=1440))]
+        minutes: u64,
+    },
+    /// Import tasks from a checklist file, keeping their done state
+    Import {
+        #[arg(long, value_enum, default_value_t = ImportFormat::Md)]
+        format: ImportFormat,
+        file: PathBuf,
+    },
+    /// Write a compacted copy of the database, by default to a timestamped
+    /// file under the data directory's backups folder
+    Backup {
+        /// Write the backup to this file instead (it must not exist yet)
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Number of timestamped backups to keep; older ones are deleted
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        keep: u32,
+    },
+    /// Check the database for corruption and compact it
+    Maintenance,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ImportFormat {
+    /// Markdown checklist items: "- [ ] task" and "- [x] task"
+    Md,
 }
 
 #[derive(Debug)]
@@ -110,12 +146,18 @@ impl App {
             app.todo_state.select(Some(0));
         }
 
+        app.restore_ui_state();
+
         Ok(app)
     }
 
-    async fn initialize_database() -> Result<SqlitePool, sqlx::Error> {
+    fn data_dir() -> PathBuf {
         let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        let db_dir = home_dir.join("todo_db");
+        home_dir.join("todo_db")
+    }
+
+    async fn initialize_database() -> Result<SqlitePool, sqlx::Error> {
+        let db_dir = Self::data_dir();
         create_dir_all(&db_dir).unwrap();
 
         let db_path = db_dir.join("todo.db");
@@ -138,6 +180,17 @@ impl App {
         .execute(&pool)
         .await?;
 
+        query(
+            "CREATE TABLE IF NOT EXISTS focus_session (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                started_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                seconds INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
         Ok(pool)
     }
 
@@ -158,6 +211,58 @@ impl App {
         Ok(tasks)
     }
 
+    /// Restores the active list and selections saved by `save_ui_state`.
+    /// Missing or malformed entries are ignored and indices are clamped to
+    /// the current number of tasks.
+    fn restore_ui_state(&mut self) {
+        let Ok(contents) = read_to_string(Self::data_dir().join(STATE_FILE)) else {
+            return;
+        };
+
+        for line in contents.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match (key.trim(), value.trim()) {
+                ("list", "todo") => self.app_state = AppState::TodoList,
+                ("list", "done") => self.app_state = AppState::DoneList,
+                ("todo_selected", index) => {
+                    if let Ok(index) = index.parse::<usize>() {
+                        let len = self.get_todo_tasks().len();
+                        self.todo_state
+                            .select((len > 0).then(|| index.min(len - 1)));
+                    }
+                }
+                ("done_selected", index) => {
+                    if let Ok(index) = index.parse::<usize>() {
+                        let len = self.get_done_tasks().len();
+                        self.done_state
+                            .select((len > 0).then(|| index.min(len - 1)));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Writes the active list and selections so the next launch reopens
+    /// at the same place.
+    fn save_ui_state(&self) -> io::Result<()> {
+        let list = match self.app_state {
+            AppState::TodoList => "todo",
+            AppState::DoneList => "done",
+        };
+        let mut contents = format!("list={}\n", list);
+        if let Some(index) = self.todo_state.selected() {
+            contents.push_str(&format!("todo_selected={}\n", index));
+        }
+        if let Some(index) = self.done_state.selected() {
+            contents.push_str(&format!("done_selected={}\n", index));
+        }
+
+        write(Self::data_dir().join(STATE_FILE), contents)
+    }
+
     fn get_todo_tasks(&self) -> Vec<&Task> {
         self.tasks.iter().filter(|task| !task.is_done).collect()
     }
@@ -588,6 +693,7 @@ async fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut app = App::new().await?;
     let res = run_app(&mut terminal, &mut app).await;
+    let saved = app.save_ui_state();
 
     disable_raw_mode()?;
     execute!(
@@ -597,6 +703,10 @@ async fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
     )?;
     terminal.show_cursor()?;
 
+    if let Err(err) = saved {
+        eprintln!("Warning: failed to save UI state: {}", err);
+    }
+
     if let Err(err) = res {
         println!("{:?}", err)
     }
@@ -611,107 +721,356 @@ async fn run_app(
     loop {
         terminal.draw(|f| ui(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match app.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('q') => return Ok(()),
-                        KeyCode::Char('u') => {
-                            let _ = app.undo().await;
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match app.input_mode {
+                InputMode::Normal => match key.code {
+                    KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char('u') => {
+                        let _ = app.undo().await;
+                    }
+                    KeyCode::Char('a') => {
+                        app.input_mode = InputMode::Adding;
+                        app.input.clear();
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => app.next_task(),
+                    KeyCode::Char('k') | KeyCode::Up => app.previous_task(),
+                    KeyCode::Char('h') | KeyCode::Left => {
+                        app.app_state = AppState::TodoList;
+                        if !app.get_todo_tasks().is_empty() && app.todo_state.selected().is_none() {
+                            app.todo_state.select(Some(0));
                         }
-                        KeyCode::Char('a') => {
-                            app.input_mode = InputMode::Adding;
-                            app.input.clear();
+                    }
+                    KeyCode::Char('l') | KeyCode::Right => {
+                        app.app_state = AppState::DoneList;
+                        if !app.get_done_tasks().is_empty() && app.done_state.selected().is_none() {
+                            app.done_state.select(Some(0));
                         }
-                        KeyCode::Char('j') | KeyCode::Down => app.next_task(),
-                        KeyCode::Char('k') | KeyCode::Up => app.previous_task(),
-                        KeyCode::Char('h') | KeyCode::Left => {
-                            app.app_state = AppState::TodoList;
-                            if !app.get_todo_tasks().is_empty()
-                                && app.todo_state.selected().is_none()
-                            {
-                                app.todo_state.select(Some(0));
-                            }
-                        }
-                        KeyCode::Char('l') | KeyCode::Right => {
-                            app.app_state = AppState::DoneList;
-                            if !app.get_done_tasks().is_empty()
-                                && app.done_state.selected().is_none()
-                            {
-                                app.done_state.select(Some(0));
-                            }
-                        }
-                        KeyCode::Char(' ') => {
-                            if let Some(task_id) = app.get_selected_task_id() {
-                                let _ = app.toggle_task(task_id).await;
-                            }
-                        }
-                        KeyCode::Char('d') => {
-                            if let Some(task_id) = app.get_selected_task_id() {
-                                let _ = app.delete_task(task_id).await;
-                            }
+                    }
+                    KeyCode::Char(' ') => {
+                        if let Some(task_id) = app.get_selected_task_id() {
+                            let _ = app.toggle_task(task_id).await;
                         }
-                        KeyCode::Char('e') => {
-                            if let Some(task_id) = app.get_selected_task_id() {
-                                app.editing_task_id = Some(task_id);
-                                app.input_mode = InputMode::Editing;
-                                if let Some(task) = app.tasks.iter().find(|t| t.id == task_id) {
-                                    app.input = task.name.clone();
-                                }
-                            }
+                    }
+                    KeyCode::Char('d') => {
+                        if let Some(task_id) = app.get_selected_task_id() {
+                            let _ = app.delete_task(task_id).await;
                         }
-                        _ => {}
-                    },
-                    InputMode::Adding => match key.code {
-                        KeyCode::Enter => {
-                            if !app.input.trim().is_empty() {
-                                let task_name = app.input.clone();
-                                let _ = app.add_task(&task_name).await;
+                    }
+                    KeyCode::Char('e') => {
+                        if let Some(task_id) = app.get_selected_task_id() {
+                            app.editing_task_id = Some(task_id);
+                            app.input_mode = InputMode::Editing;
+                            if let Some(task) = app.tasks.iter().find(|t| t.id == task_id) {
+                                app.input = task.name.clone();
                             }
-                            app.input.clear();
-                            app.input_mode = InputMode::Normal;
                         }
-                        KeyCode::Char(c) => {
-                            app.input.push(c);
+                    }
+                    _ => {}
+                },
+                InputMode::Adding => match key.code {
+                    KeyCode::Enter => {
+                        if !app.input.trim().is_empty() {
+                            let task_name = app.input.clone();
+                            let _ = app.add_task(&task_name).await;
                         }
-                        KeyCode::Backspace => {
-                            app.input.pop();
+                        app.input.clear();
+                        app.input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Char(c) => {
+                        app.input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.input.pop();
+                    }
+                    KeyCode::Esc => {
+                        app.input.clear();
+                        app.input_mode = InputMode::Normal;
+                    }
+                    _ => {}
+                },
+                InputMode::Editing => match key.code {
+                    KeyCode::Enter => {
+                        if !app.input.trim().is_empty()
+                            && let Some(task_id) = app.editing_task_id
+                        {
+                            let task_name = app.input.clone();
+                            let _ = app.update_task(task_id, &task_name).await;
                         }
-                        KeyCode::Esc => {
-                            app.input.clear();
-                            app.input_mode = InputMode::Normal;
-                        }
-                        _ => {}
-                    },
-                    InputMode::Editing => match key.code {
-                        KeyCode::Enter => {
-                            if !app.input.trim().is_empty() {
-                                if let Some(task_id) = app.editing_task_id {
-                                    let task_name = app.input.clone();
-                                    let _ = app.update_task(task_id, &task_name).await;
-                                }
-                            }
-                            app.input.clear();
-                            app.editing_task_id = None;
-                            app.input_mode = InputMode::Normal;
-                        }
-                        KeyCode::Char(c) => {
-                            app.input.push(c);
-                        }
-                        KeyCode::Backspace => {
-                            app.input.pop();
-                        }
-                        KeyCode::Esc => {
-                            app.input.clear();
-                            app.editing_task_id = None;
-                            app.input_mode = InputMode::Normal;
-                        }
-                        _ => {}
-                    },
+                        app.input.clear();
+                        app.editing_task_id = None;
+                        app.input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Char(c) => {
+                        app.input.push(c);
+                    }
+                    KeyCode::Backspace => {
+                        app.input.pop();
+                    }
+                    KeyCode::Esc => {
+                        app.input.clear();
+                        app.editing_task_id = None;
+                        app.input_mode = InputMode::Normal;
+                    }
+                    _ => {}
+                },
+            }
+        }
+    }
+}
+
+async fn run_focus(id: u32, minutes: u64) -> Result<(), Box<dyn std::error::Error>> {
+    let pool = App::initialize_database().await?;
+    let task = query("SELECT name, is_done FROM todo WHERE id = ?")
+        .bind(id)
+        .fetch_optional(&pool)
+        .await?
+        .ok_or_else(|| format!("No task with id {}", id))?;
+    let name: String = task.get("name");
+
+    if task.get::<i64, _>("is_done") == 1 {
+        println!("Task \"{}\" is already done", name);
+        return Ok(());
+    }
+
+    let total = Duration::from_secs(minutes * 60);
+    let started = Instant::now();
+    let mut ticker = tokio::time::interval(Duration::from_secs(1));
+    let mut stdout = io::stdout();
+
+    println!(
+        "Focusing on \"{}\" for {} minutes (Ctrl-C to stop early)",
+        name, minutes
+    );
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let remaining = total.saturating_sub(started.elapsed()).as_secs();
+                print!("\r{:02}:{:02} remaining", remaining / 60, remaining % 60);
+                stdout.flush()?;
+                if remaining == 0 {
+                    break;
                 }
             }
+            _ = tokio::signal::ctrl_c() => break,
         }
     }
+    println!();
+
+    let spent = started.elapsed().min(total).as_secs();
+    query("INSERT INTO focus_session (task_id, seconds) VALUES (?, ?)")
+        .bind(id)
+        .bind(spent as i64)
+        .execute(&pool)
+        .await?;
+
+    let total_spent: i64 =
+        query("SELECT COALESCE(SUM(seconds), 0) AS total FROM focus_session WHERE task_id = ?")
+            .bind(id)
+            .fetch_one(&pool)
+            .await?
+            .get("total");
+    println!(
+        "Logged {} on \"{}\" ({} in total)",
+        format_duration(spent),
+        name,
+        format_duration(total_spent as u64)
+    );
+
+    print!("Mark task as complete? [y/N] ");
+    stdout.flush()?;
+    // The countdown's Ctrl-C listener replaced the default handler, so the
+    // prompt has to listen for it too or the user could not abort here.
+    let read_answer = tokio::task::spawn_blocking(|| {
+        let mut answer = String::new();
+        io::stdin().read_line(&mut answer).map(|_| answer)
+    });
+    let answer = tokio::select! {
+        answer = read_answer => answer??,
+        _ = tokio::signal::ctrl_c() => {
+            println!();
+            // The blocking stdin read cannot be cancelled, so exit directly
+            // instead of waiting for the runtime to shut down.
+            std::process::exit(130);
+        }
+    };
+    if matches!(answer.trim(), "y" | "Y" | "yes") {
+        query("UPDATE todo SET is_done = 1 WHERE id = ?")
+            .bind(id)
+            .execute(&pool)
+            .await?;
+        println!("Completed \"{}\"", name);
+    }
+
+    Ok(())
+}
+
+async fn run_add(task: String) -> Result<(), Box<dyn std::error::Error>> {
+    let names: Vec<String> = if task == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect()
+    } else {
+        vec![task]
+    };
+
+    let added = insert_tasks(names.into_iter().map(|name| (name, false))).await?;
+    println!("Added {} task(s)", added);
+    Ok(())
+}
+
+async fn run_import(format: ImportFormat, file: PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+    let content =
+        read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let tasks = match format {
+        ImportFormat::Md => parse_markdown_checklist(&content),
+    };
+
+    let done = tasks.iter().filter(|(_, is_done)| *is_done).count();
+    let imported = insert_tasks(tasks).await?;
+    println!("Imported {} task(s), {} already done", imported, done);
+    Ok(())
+}
+
+async fn run_backup(out: Option<PathBuf>, keep: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let pool = App::initialize_database().await?;
+    let backup_dir = App::data_dir().join(BACKUP_DIR);
+    let path = match &out {
+        Some(path) => path.clone(),
+        None => {
+            create_dir_all(&backup_dir)?;
+            let stamp: String = query("SELECT strftime('%Y%m%d-%H%M%S', 'now') AS stamp")
+                .fetch_one(&pool)
+                .await?
+                .get("stamp");
+            backup_dir.join(format!("{}{}.db", BACKUP_PREFIX, stamp))
+        }
+    };
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+
+    // VACUUM INTO writes a consistent, compacted copy even while the
+    // database is open elsewhere.
+    query("VACUUM INTO ?")
+        .bind(path.to_string_lossy().to_string())
+        .execute(&pool)
+        .await?;
+    println!("Backed up database to {}", path.display());
+
+    if out.is_none() {
+        let removed = prune_backups(&backup_dir, keep as usize)?;
+        if removed > 0 {
+            println!("Removed {} old backup(s)", removed);
+        }
+    }
+    Ok(())
+}
+
+/// Deletes all but the newest `keep` timestamped backups. The timestamp in
+/// the file name sorts chronologically.
+fn prune_backups(dir: &Path, keep: usize) -> io::Result<usize> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(BACKUP_PREFIX) && name.ends_with(".db"))
+        })
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(keep);
+    for path in &backups[..excess] {
+        std::fs::remove_file(path)?;
+    }
+    Ok(excess)
+}
+
+async fn run_maintenance() -> Result<(), Box<dyn std::error::Error>> {
+    let pool = App::initialize_database().await?;
+    let db_path = App::data_dir().join("todo.db");
+
+    let problems: Vec<String> = query("PRAGMA integrity_check")
+        .fetch_all(&pool)
+        .await?
+        .into_iter()
+        .map(|row| row.get::<String, _>(0))
+        .filter(|result| result != "ok")
+        .collect();
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        return Err(format!(
+            "Integrity check found {} problem(s); restore from a backup",
+            problems.len()
+        )
+        .into());
+    }
+    println!("Integrity check passed");
+
+    let before = std::fs::metadata(&db_path)?.len();
+    query("VACUUM").execute(&pool).await?;
+    let after = std::fs::metadata(&db_path)?.len();
+    println!("Compacted database from {} to {} bytes", before, after);
+    Ok(())
+}
+
+/// Inserts all tasks in one transaction, so a failed import adds nothing.
+async fn insert_tasks(
+    tasks: impl IntoIterator<Item = (String, bool)>,
+) -> Result<usize, sqlx::Error> {
+    let pool = App::initialize_database().await?;
+    let mut tx = pool.begin().await?;
+    let mut count = 0;
+    for (name, is_done) in tasks {
+        query("INSERT INTO todo (name, is_done) VALUES (?, ?)")
+            .bind(name)
+            .bind(is_done as i64)
+            .execute(&mut *tx)
+            .await?;
+        count += 1;
+    }
+    tx.commit().await?;
+    Ok(count)
+}
+
+/// Collects "- [ ] task" / "- [x] task" items (also with "*" or "+"
+/// bullets, at any indentation). Every other line is ignored.
+fn parse_markdown_checklist(content: &str) -> Vec<(String, bool)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let item = line
+                .trim_start()
+                .strip_prefix(['-', '*', '+'])?
+                .strip_prefix(' ')?
+                .trim_start();
+            let (is_done, name) = if let Some(name) = item.strip_prefix("[ ]") {
+                (false, name)
+            } else if let Some(name) = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"))
+            {
+                (true, name)
+            } else {
+                return None;
+            };
+            let name = name.trim();
+            (!name.is_empty()).then(|| (name.to_string(), is_done))
+        })
+        .collect()
+}
+
+fn format_duration(seconds: u64) -> String {
+    format!("{}m {:02}s", seconds / 60, seconds % 60)
 }
 
 #[tokio::main]
@@ -719,6 +1078,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
     match args.command {
+        Some(Commands::Focus { id, minutes }) => {
+            run_focus(id, minutes).await?;
+        }
+        Some(Commands::Add { task }) => {
+            run_add(task).await?;
+        }
+        Some(Commands::Import { format, file }) => {
+            run_import(format, file).await?;
+        }
+        Some(Commands::Backup { out, keep }) => {
+            run_backup(out, keep).await?;
+        }
+        Some(Commands::Maintenance) => {
+            run_maintenance().await?;
+        }
         Some(_command) => {
             // Fixed: Prefixed with underscore to indicate intentional non-use
             println!("CLI mode: Use without arguments to start TUI mode");
@@ -0,0 +1,115 @@
+//! On-disk cache of inspection results.
+//!
+//! Entries are keyed by the resolved path and are only reused when the
+//! inspected file's size, modification time and code directory hash are
+//! unchanged.
+
+use crate::SignatureInfo;
+use serde::{
+    Deserialize,
+    Serialize, //
+};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{
+    Path,
+    PathBuf, //
+};
+use std::time::UNIX_EPOCH;
+
+const CACHE_FILE: &str = "results.json";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    size: u64,
+    modified_secs: u64,
+    modified_nanos: u32,
+    cdhash: String,
+}
+
+impl Fingerprint {
+    /// Builds a fingerprint for `path`, or `None` if the file has no code
+    /// directory hash or its metadata can't be read.
+    pub fn new(path: &Path, cdhash: &str) -> Option<Self> {
+        if cdhash.is_empty() {
+            return None;
+        }
+
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+
+        Some(Self {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            cdhash: cdhash.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fingerprint: Fingerprint,
+    info: SignatureInfo,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ResultCache {
+    entries: HashMap<String, CacheEntry>,
+}
+
+impl ResultCache {
+    /// Returns `$XDG_CACHE_HOME/whatsyoursign`, falling back to
+    /// `~/Library/Caches/whatsyoursign`.
+    fn dir() -> Option<PathBuf> {
+        env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Caches")))
+            .map(|dir| dir.join("whatsyoursign"))
+    }
+
+    /// Loads the cache, treating a missing or unreadable file as empty.
+    pub fn load() -> Self {
+        Self::dir()
+            .and_then(|dir| fs::read_to_string(dir.join(CACHE_FILE)).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, key: &str, fingerprint: &Fingerprint) -> Option<SignatureInfo> {
+        self.entries
+            .get(key)
+            .filter(|entry| entry.fingerprint == *fingerprint)
+            .map(|entry| entry.info.clone())
+    }
+
+    pub fn insert(&mut self, key: String, fingerprint: Fingerprint, info: &SignatureInfo) {
+        self.entries.insert(
+            key,
+            CacheEntry {
+                fingerprint,
+                info: info.clone(),
+            },
+        );
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let dir = Self::dir().ok_or_else(|| io::Error::other("no cache directory"))?;
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(CACHE_FILE), serde_json::to_string(self)?)
+    }
+
+    /// Deletes the cache file. Returns `false` if there was nothing to delete.
+    pub fn clear() -> io::Result<bool> {
+        let Some(dir) = Self::dir() else {
+            return Ok(false);
+        };
+        match fs::remove_file(dir.join(CACHE_FILE)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
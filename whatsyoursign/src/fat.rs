@@ -0,0 +1,229 @@
+//! Per-architecture inspection of universal (fat) Mach-O binaries.
+//!
+//! `codesign` reports on the slice matching the host by default, so each
+//! slice listed in the fat header is checked separately with `--arch`.
+
+use serde::{
+    Deserialize,
+    Serialize, //
+};
+use std::fs::File;
+use std::io::{
+    self,
+    Read, //
+};
+use std::path::Path;
+use std::process::Command;
+
+const FAT_MAGIC: u32 = 0xcafe_babe;
+const FAT_MAGIC_64: u32 = 0xcafe_babf;
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+const CPU_TYPE_X86: u32 = 7;
+const CPU_TYPE_ARM: u32 = 12;
+const CPU_TYPE_POWERPC: u32 = 18;
+const CPU_SUBTYPE_MASK: u32 = 0x00ff_ffff;
+const CPU_SUBTYPE_ARM64E: u32 = 2;
+
+/// Java class files share the fat magic; they are told apart by the slice
+/// count, which for class files is a version number of 45 or more.
+const MAX_FAT_SLICES: u32 = 30;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SliceInfo {
+    pub arch: String,
+    pub is_valid: bool,
+    pub identifier: String,
+    pub team_identifier: String,
+    pub code_directory: String,
+}
+
+/// Reads the architecture names from a fat header. Thin binaries and
+/// non-Mach-O files yield an empty list.
+pub fn architectures(path: &Path) -> io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let mut header = [0u8; 8];
+    if file.read_exact(&mut header).is_err() {
+        return Ok(Vec::new());
+    }
+
+    let magic = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let count = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+    let entry_size = match magic {
+        FAT_MAGIC => 20,
+        FAT_MAGIC_64 => 32,
+        _ => return Ok(Vec::new()),
+    };
+    if count == 0 || count > MAX_FAT_SLICES {
+        return Ok(Vec::new());
+    }
+
+    let mut archs = Vec::new();
+    let mut entry = vec![0u8; entry_size];
+    for _ in 0..count {
+        file.read_exact(&mut entry)?;
+        let cpu_type = u32::from_be_bytes([entry[0], entry[1], entry[2], entry[3]]);
+        let cpu_subtype = u32::from_be_bytes([entry[4], entry[5], entry[6], entry[7]]);
+        archs.push(arch_name(cpu_type, cpu_subtype));
+    }
+    Ok(archs)
+}
+
+/// Maps a CPU type to the name `codesign --arch` accepts.
+fn arch_name(cpu_type: u32, cpu_subtype: u32) -> String {
+    match cpu_type {
+        CPU_TYPE_X86 => "i386".to_string(),
+        t if t == CPU_TYPE_X86 | CPU_ARCH_ABI64 => "x86_64".to_string(),
+        CPU_TYPE_ARM => "arm".to_string(),
+        t if t == CPU_TYPE_ARM | CPU_ARCH_ABI64 => {
+            if cpu_subtype & CPU_SUBTYPE_MASK == CPU_SUBTYPE_ARM64E {
+                "arm64e".to_string()
+            } else {
+                "arm64".to_string()
+            }
+        }
+        CPU_TYPE_POWERPC => "ppc".to_string(),
+        t if t == CPU_TYPE_POWERPC | CPU_ARCH_ABI64 => "ppc64".to_string(),
+        other => format!("cputype {other}"),
+    }
+}
+
+/// Runs `codesign` against a single slice.
+pub fn inspect_slice(path: &str, arch: &str) -> io::Result<SliceInfo> {
+    let display = Command::new("codesign")
+        .args(["-dvvv", "--arch", arch, path])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&display.stderr);
+
+    let value = |key: &str| {
+        stderr
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .unwrap_or("")
+            .to_string()
+    };
+
+    let verify = Command::new("codesign")
+        .args(["-vv", "--arch", arch, path])
+        .output()?;
+
+    Ok(SliceInfo {
+        arch: arch.to_string(),
+        is_valid: display.status.success() && verify.status.success(),
+        identifier: value("Identifier="),
+        team_identifier: value("TeamIdentifier="),
+        code_directory: crate::code_directory_hash(&stderr),
+    })
+}
+
+/// Describes how the slices disagree with each other, if at all.
+pub fn mismatches(slices: &[SliceInfo]) -> Vec<String> {
+    let mut found = Vec::new();
+    let Some(first) = slices.first() else {
+        return found;
+    };
+
+    let differs = |field: fn(&SliceInfo) -> &str| slices.iter().any(|s| field(s) != field(first));
+    if slices.iter().any(|s| s.is_valid != first.is_valid) {
+        found.push("slices differ in signing status".to_string());
+    }
+    if differs(|s| &s.identifier) {
+        found.push("slices are signed with different identifiers".to_string());
+    }
+    if differs(|s| &s.team_identifier) {
+        found.push("slices are signed by different teams".to_string());
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Big-endian words of a fat header followed by its slice entries, each
+    /// padded to `entry_size` bytes.
+    fn fat_header(magic: u32, slices: &[(u32, u32)], entry_size: usize) -> Vec<u8> {
+        let mut data = magic.to_be_bytes().to_vec();
+        data.extend_from_slice(&u32::try_from(slices.len()).unwrap().to_be_bytes());
+        for &(cpu_type, cpu_subtype) in slices {
+            let mut entry = vec![0u8; entry_size];
+            entry[..4].copy_from_slice(&cpu_type.to_be_bytes());
+            entry[4..8].copy_from_slice(&cpu_subtype.to_be_bytes());
+            data.extend_from_slice(&entry);
+        }
+        data
+    }
+
+    fn read_archs(name: &str, data: &[u8]) -> io::Result<Vec<String>> {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("whatsyoursign-fat-{}-{name}", std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        let archs = architectures(&path);
+        std::fs::remove_file(&path).unwrap();
+        archs
+    }
+
+    fn archs_of(name: &str, data: &[u8]) -> Vec<String> {
+        read_archs(name, data).unwrap()
+    }
+
+    #[test]
+    fn reads_32_bit_fat_header() {
+        let data = fat_header(
+            FAT_MAGIC,
+            &[
+                (CPU_TYPE_X86 | CPU_ARCH_ABI64, 3),
+                (CPU_TYPE_ARM | CPU_ARCH_ABI64, 0),
+            ],
+            20,
+        );
+        assert_eq!(archs_of("fat32", &data), ["x86_64", "arm64"]);
+    }
+
+    #[test]
+    fn reads_64_bit_fat_header() {
+        let data = fat_header(
+            FAT_MAGIC_64,
+            &[
+                (CPU_TYPE_X86, 3),
+                (CPU_TYPE_POWERPC, 0),
+                (CPU_TYPE_POWERPC | CPU_ARCH_ABI64, 0),
+            ],
+            32,
+        );
+        assert_eq!(archs_of("fat64", &data), ["i386", "ppc", "ppc64"]);
+    }
+
+    #[test]
+    fn masks_arm64e_capability_bits() {
+        let arm64 = CPU_TYPE_ARM | CPU_ARCH_ABI64;
+        assert_eq!(arch_name(arm64, 0x8000_0000 | CPU_SUBTYPE_ARM64E), "arm64e");
+        assert_eq!(arch_name(arm64, CPU_SUBTYPE_ARM64E), "arm64e");
+        assert_eq!(arch_name(arm64, 0x8000_0000), "arm64");
+        assert_eq!(arch_name(CPU_TYPE_ARM, 9), "arm");
+        assert_eq!(arch_name(99, 0), "cputype 99");
+    }
+
+    #[test]
+    fn rejects_java_class_files() {
+        // Magic followed by minor version 0 and major version 52 (Java 8).
+        let data = [0xca, 0xfe, 0xba, 0xbe, 0x00, 0x00, 0x00, 0x34, 0x00, 0x1d];
+        assert!(archs_of("class", &data).is_empty());
+    }
+
+    #[test]
+    fn thin_and_short_files_have_no_slices() {
+        // 64-bit Mach-O header magic of a thin binary.
+        assert!(archs_of("thin", &[0xcf, 0xfa, 0xed, 0xfe, 0x0c, 0, 0, 0x01]).is_empty());
+        assert!(archs_of("short", &[0xca, 0xfe, 0xba]).is_empty());
+        assert!(archs_of("empty", &[]).is_empty());
+        assert!(archs_of("none", &fat_header(FAT_MAGIC, &[], 20)).is_empty());
+    }
+
+    #[test]
+    fn truncated_slice_table_is_an_error() {
+        let mut data = fat_header(FAT_MAGIC, &[(CPU_TYPE_X86, 3), (CPU_TYPE_ARM, 9)], 20);
+        data.truncate(data.len() - 1);
+        assert!(read_archs("truncated", &data).is_err());
+    }
+}
@@ -0,0 +1,307 @@
+//! Message catalog for the human-readable output and error guidance.
+//!
+//! Plain and JSON output are never translated, so scripts can keep parsing
+//! them regardless of the user's locale.
+
+use std::env;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Lang {
+    En,
+    Vi,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+impl Lang {
+    /// Picks the language from `LC_ALL`, `LC_MESSAGES` or `LANG`, in that
+    /// order of precedence, falling back to English.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .find_map(|key| env::var(key).ok().filter(|value| !value.is_empty()))
+            .map_or(Self::En, |value| {
+                if value.starts_with("vi") {
+                    Self::Vi
+                } else {
+                    Self::En
+                }
+            })
+    }
+}
+
+/// Sets the language for the rest of the run. Only the first call counts.
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+fn lang() -> Lang {
+    *LANG.get_or_init(Lang::from_env)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Msg {
+    // Human output.
+    Inspecting,
+    StatusValidNotarized,
+    StatusValid,
+    StatusInvalid,
+    ResolvedTo,
+    Identifier,
+    Format,
+    CodeDirectory,
+    Status,
+    Authority,
+    Notarization,
+    Architectures,
+    Hashes,
+    Entitlements,
+    Application,
+    Executable,
+    Unknown,
+    NotAvailable,
+    Stapled,
+    StapledBound,
+    TicketSigned,
+    TicketInvalid,
+    UnknownProblem,
+    Warning,
+    CacheCleared,
+    CacheEmpty,
+    Resources,
+    ResourcesIntact,
+    ResourcesResign,
+    ResourceAdded,
+    ResourceModified,
+    ResourceMissing,
+    ResourceInvalid,
+    ResourceValidated,
+
+    // Error guidance.
+    Error,
+    NotSigned,
+    NotAccessible,
+    CodesignFailed,
+    UnknownError,
+    SuggestSignedBinary,
+    ImportantSignedBinary,
+    PathNotFound,
+    SuggestCheckPath,
+    SuggestMissingDirectory,
+    SuggestMissingFile,
+    ImportantCheckPath,
+    MissingTools,
+    SuggestInstallXcode,
+    ImportantInstallXcode,
+    CommandFailed,
+    CommandNotFound,
+    PermissionDenied,
+    OtherError,
+    SuggestUnsigned,
+    ImportantCommandAccess,
+    UnexpectedError,
+    RunWithDebug,
+    ReportBug,
+    IncludeInfo,
+    InfoCommand,
+    InfoMessage,
+    InfoDebugOutput,
+    InfoMacosVersion,
+    ImportantBug,
+    MacosOnly,
+    SuggestMacos,
+    ImportantMacos,
+    WhileClearingCache,
+    WhileInspecting,
+}
+
+impl Msg {
+    /// English and Vietnamese text. `{name}` placeholders are filled in by
+    /// [`tr_with`].
+    #[allow(clippy::too_many_lines)]
+    const fn texts(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Inspecting => ("Inspecting signature...", "Đang kiểm tra chữ ký..."),
+            Self::StatusValidNotarized => ("Valid & Notarized", "Hợp lệ & Đã công chứng"),
+            Self::StatusValid => ("Valid", "Hợp lệ"),
+            Self::StatusInvalid => ("Invalid", "Không hợp lệ"),
+            Self::ResolvedTo => ("Resolved to", "Trỏ tới"),
+            Self::Identifier => ("Identifier", "Định danh"),
+            Self::Format => ("Format", "Định dạng"),
+            Self::CodeDirectory => ("CodeDirectory", "CodeDirectory"),
+            Self::Status => ("Status", "Trạng thái"),
+            Self::Authority => ("Authority", "Chứng thực"),
+            Self::Notarization => ("Notarization", "Công chứng"),
+            Self::Architectures => ("Architectures", "Kiến trúc"),
+            Self::Hashes => ("Hashes", "Mã băm"),
+            Self::Entitlements => ("Entitlements", "Quyền hạn"),
+            Self::Application => ("Application", "Ứng dụng"),
+            Self::Executable => ("Executable", "Tệp thực thi"),
+            Self::Unknown => ("Unknown", "Không rõ"),
+            Self::NotAvailable => ("N/A", "Không có"),
+            Self::Stapled => ("Stapled", "Đã đính kèm vé"),
+            Self::StapledBound => (
+                "Stapled (ticket bound to CDHash{date})",
+                "Đã đính kèm vé (vé khớp CDHash{date})",
+            ),
+            Self::TicketSigned => (", signed {date}", ", ký ngày {date}"),
+            Self::TicketInvalid => (
+                "Stapled ticket is invalid: {problem}",
+                "Vé đính kèm không hợp lệ: {problem}",
+            ),
+            Self::UnknownProblem => ("unknown problem", "lỗi không rõ"),
+            Self::Warning => ("Warning: {message}", "Cảnh báo: {message}"),
+            Self::CacheCleared => ("Cache cleared.", "Đã xóa bộ nhớ đệm."),
+            Self::CacheEmpty => ("Cache is already empty.", "Bộ nhớ đệm đã trống."),
+            Self::Resources => ("Resources", "Tài nguyên"),
+            Self::ResourcesIntact => (
+                "All sealed resources are intact",
+                "Mọi tài nguyên được niêm phong đều nguyên vẹn",
+            ),
+            Self::ResourcesResign => (
+                "Re-sign the bundle with a current codesign to rebuild its resource envelope.",
+                "Hãy ký lại gói bằng codesign mới để tạo lại phần niêm phong tài nguyên.",
+            ),
+            Self::ResourceAdded => ("added", "thêm mới"),
+            Self::ResourceModified => ("modified", "đã sửa"),
+            Self::ResourceMissing => ("missing", "bị thiếu"),
+            Self::ResourceInvalid => ("invalid", "không hợp lệ"),
+            Self::ResourceValidated => ("validated", "hợp lệ"),
+
+            Self::Error => ("Error", "Lỗi"),
+            Self::NotSigned => (
+                "The file '{path}' is not code signed. This tool only works with signed macOS applications and executables.",
+                "Tệp '{path}' chưa được ký mã. Công cụ này chỉ hoạt động với ứng dụng và tệp thực thi macOS đã ký.",
+            ),
+            Self::NotAccessible => (
+                "The file '{path}' doesn't exist or can't be accessed.",
+                "Tệp '{path}' không tồn tại hoặc không thể truy cập.",
+            ),
+            Self::CodesignFailed => ("codesign failed: {reason}", "codesign thất bại: {reason}"),
+            Self::UnknownError => ("Unknown error", "Lỗi không rõ"),
+            Self::SuggestSignedBinary => (
+                "Make sure the file is a signed macOS application (.app) or executable binary.",
+                "Hãy đảm bảo tệp là ứng dụng macOS (.app) hoặc tệp thực thi đã được ký.",
+            ),
+            Self::ImportantSignedBinary => (
+                "Most important: The file must be a signed macOS binary to inspect its signature.",
+                "Quan trọng nhất: Tệp phải là tệp nhị phân macOS đã ký thì mới kiểm tra được chữ ký.",
+            ),
+            Self::PathNotFound => (
+                "Can't find the file or directory:",
+                "Không tìm thấy tệp hoặc thư mục:",
+            ),
+            Self::SuggestCheckPath => (
+                "Make sure the path is correct and the file exists.",
+                "Hãy đảm bảo đường dẫn đúng và tệp tồn tại.",
+            ),
+            Self::SuggestMissingDirectory => (
+                "The directory '{directory}' doesn't exist. Make sure the path is correct.",
+                "Thư mục '{directory}' không tồn tại. Hãy kiểm tra lại đường dẫn.",
+            ),
+            Self::SuggestMissingFile => (
+                "The path exists but the file '{file}' doesn't. Check the spelling and try again.",
+                "Thư mục tồn tại nhưng tệp '{file}' thì không. Hãy kiểm tra chính tả và thử lại.",
+            ),
+            Self::ImportantCheckPath => (
+                "Most important: Check that the path is correct and the file exists.",
+                "Quan trọng nhất: Kiểm tra xem đường dẫn có đúng và tệp có tồn tại không.",
+            ),
+            Self::MissingTools => ("Missing required tools:", "Thiếu công cụ bắt buộc:"),
+            Self::SuggestInstallXcode => (
+                "Install Xcode Command Line Tools by running:",
+                "Cài đặt Xcode Command Line Tools bằng lệnh:",
+            ),
+            Self::ImportantInstallXcode => (
+                "Most important: Install Xcode Command Line Tools to use this tool.",
+                "Quan trọng nhất: Cài đặt Xcode Command Line Tools để dùng công cụ này.",
+            ),
+            Self::CommandFailed => (
+                "Failed to run '{command}' on:",
+                "Không chạy được '{command}' trên:",
+            ),
+            Self::CommandNotFound => (
+                "The '{command}' command was not found.",
+                "Không tìm thấy lệnh '{command}'.",
+            ),
+            Self::PermissionDenied => (
+                "Permission denied. You might need to make the file readable by running:\n  chmod +r \"{path}\"",
+                "Không có quyền truy cập. Có thể bạn cần cho phép đọc tệp bằng lệnh:\n  chmod +r \"{path}\"",
+            ),
+            Self::OtherError => ("Error: {error}", "Lỗi: {error}"),
+            Self::SuggestUnsigned => (
+                "The file might not be a signed macOS application or executable.",
+                "Có thể tệp không phải là ứng dụng hoặc tệp thực thi macOS đã ký.",
+            ),
+            Self::ImportantCommandAccess => (
+                "Most important: Make sure '{command}' can access the file and it's a valid macOS binary.",
+                "Quan trọng nhất: Hãy đảm bảo '{command}' truy cập được tệp và đó là tệp nhị phân macOS hợp lệ.",
+            ),
+            Self::UnexpectedError => (
+                "An unexpected error occurred:",
+                "Đã xảy ra lỗi không mong muốn:",
+            ),
+            Self::RunWithDebug => (
+                "Run with --debug to see detailed error information.",
+                "Chạy lại với --debug để xem chi tiết lỗi.",
+            ),
+            Self::ReportBug => (
+                "This looks like a bug. Please report it:",
+                "Có vẻ đây là lỗi của chương trình. Vui lòng báo cáo tại:",
+            ),
+            Self::IncludeInfo => (
+                "Include the following information:",
+                "Kèm theo các thông tin sau:",
+            ),
+            Self::InfoCommand => ("The command you ran", "Lệnh bạn đã chạy"),
+            Self::InfoMessage => ("The error message above", "Thông báo lỗi ở trên"),
+            Self::InfoDebugOutput => (
+                "Output from running with --debug flag",
+                "Kết quả khi chạy với cờ --debug",
+            ),
+            Self::InfoMacosVersion => ("Your macOS version", "Phiên bản macOS của bạn"),
+            Self::ImportantBug => (
+                "Most important: This is a bug. Please report it with the information above.",
+                "Quan trọng nhất: Đây là lỗi của chương trình. Vui lòng báo cáo kèm các thông tin trên.",
+            ),
+            Self::MacosOnly => (
+                "This tool only works on macOS.",
+                "Công cụ này chỉ hoạt động trên macOS.",
+            ),
+            Self::SuggestMacos => (
+                "Run this tool on a macOS system to inspect code signatures.",
+                "Hãy chạy công cụ này trên macOS để kiểm tra chữ ký mã.",
+            ),
+            Self::ImportantMacos => (
+                "Most important: This tool requires macOS to function.",
+                "Quan trọng nhất: Công cụ này cần macOS để hoạt động.",
+            ),
+            Self::WhileClearingCache => ("while clearing the cache", "khi xóa bộ nhớ đệm"),
+            Self::WhileInspecting => ("while inspecting signature", "khi kiểm tra chữ ký"),
+        }
+    }
+}
+
+/// Returns `msg` in the current language.
+pub fn tr(msg: Msg) -> &'static str {
+    let (en, vi) = msg.texts();
+    match lang() {
+        Lang::En => en,
+        Lang::Vi => vi,
+    }
+}
+
+/// Returns `msg` in the current language with each `{name}` placeholder
+/// replaced by its value.
+pub fn tr_with(msg: Msg, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(tr(msg).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Returns a field label padded to line up human output values.
+pub fn label(msg: Msg) -> String {
+    format!("{:<18}", format!("{}:", tr(msg)))
+}
@@ -1,9 +1,37 @@
-use clap::Parser;
+mod cache;
+mod fat;
+mod i18n;
+mod resources;
+mod ticket;
+
+use cache::{
+    Fingerprint,
+    ResultCache, //
+};
+use clap::{
+    Parser,
+    Subcommand, //
+};
+use fat::SliceInfo;
+use i18n::{
+    Lang,
+    Msg,
+    label,
+    tr,
+    tr_with, //
+};
 use owo_colors::{
     OwoColorize,
     Style, //
 };
-use serde::Serialize;
+use resources::{
+    ResourceReport,
+    ResourceStatus, //
+};
+use serde::{
+    Deserialize,
+    Serialize, //
+};
 use std::env;
 use std::fmt::Write;
 use std::io::{
@@ -19,6 +47,7 @@ use std::process::{
     ExitCode,
     Stdio, //
 };
+use ticket::StapledTicket;
 use which::which;
 
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
@@ -51,7 +80,7 @@ impl ColorConfig {
         }
 
         // Check if `TERM` is "dumb".
-        if env::var("TERM").map(|term| term == "dumb").unwrap_or(false) {
+        if env::var("TERM").is_ok_and(|term| term == "dumb") {
             return false;
         }
 
@@ -64,7 +93,7 @@ impl ColorConfig {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 enum AppFormat {
     Application,
     Executable,
@@ -81,25 +110,15 @@ impl std::fmt::Display for AppFormat {
     }
 }
 
-impl Serialize for AppFormat {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match self {
-            Self::Application => serializer.serialize_str("Application"),
-            Self::Executable => serializer.serialize_str("Executable"),
-            Self::Unknown => serializer.serialize_str("Unknown"),
-        }
-    }
-}
-
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Path to the file to inspect.
-    #[arg(short, long)]
-    path: String,
+    #[arg(short, long, required = true)]
+    path: Option<String>,
 
     /// Output format.
     #[arg(long, value_enum, default_value = "human")]
@@ -116,8 +135,36 @@ struct Args {
     /// Show detailed debug information for errors.
     #[arg(long)]
     debug: bool,
+
+    /// Always inspect the file instead of reusing a cached result.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Validate nested code and sealed resources deeply and list the files that changed.
+    #[arg(long)]
+    verify_resources: bool,
+
+    /// Language of the human-readable output and messages (defaults to `LANG`).
+    #[arg(long, value_enum)]
+    lang: Option<Lang>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Manage the cache of inspection results.
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Delete all cached inspection results.
+    Clear,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct HashInfo {
     md5: String,
     sha1: String,
@@ -136,6 +183,7 @@ struct HashInfoJson {
     code_directory: String,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 struct SignatureInfo {
     identifier: String,
     name: String,
@@ -148,6 +196,12 @@ struct SignatureInfo {
     authorities: Vec<String>,
     hashes: Option<HashInfo>,
     entitlements: Option<String>,
+    #[serde(default)]
+    slices: Vec<SliceInfo>, // Per-architecture results for universal binaries.
+    #[serde(default)]
+    ticket: Option<StapledTicket>, // Stapled notarization ticket of the app bundle.
+    #[serde(skip)]
+    resources: Option<ResourceReport>, // Deep validation, never cached.
 }
 
 #[derive(Serialize)]
@@ -166,6 +220,14 @@ struct SignatureInfoJson {
     hashes: Option<HashInfoJson>,
     #[serde(skip_serializing_if = "Option::is_none")]
     entitlements: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    slices: Vec<SliceInfo>,
+    #[serde(rename = "slice_mismatches", skip_serializing_if = "Vec::is_empty")]
+    slice_mismatches: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ticket: Option<StapledTicket>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resources: Option<ResourceReport>,
 }
 
 impl SignatureInfo {
@@ -189,6 +251,10 @@ impl SignatureInfo {
                 .entitlements
                 .as_ref()
                 .and_then(|e| serde_json::from_str(e).ok()),
+            slices: self.slices.clone(),
+            slice_mismatches: fat::mismatches(&self.slices),
+            ticket: self.ticket.clone(),
+            resources: self.resources.clone(),
         }
     }
 }
@@ -249,14 +315,14 @@ fn parse_codesign_output(stderr: &str) -> (SignatureInfo, Option<String>) {
     };
 
     // Extract name from identifier (remove `com.` prefix and company name).
-    let name: String = if !identifier.contains('.') {
-        identifier.clone()
-    } else {
+    let name: String = if identifier.contains('.') {
         let last = identifier.split('.').next_back().unwrap_or(&identifier);
         let mut chars = last.chars();
         chars.next().map_or_else(String::new, |first| {
             first.to_uppercase().chain(chars).collect()
         })
+    } else {
+        identifier.clone()
     };
 
     // Determine type from format.
@@ -281,6 +347,9 @@ fn parse_codesign_output(stderr: &str) -> (SignatureInfo, Option<String>) {
             authorities,
             hashes: None,       // Will be set from hash commands.
             entitlements: None, // Will be set from entitlements command.
+            slices: Vec::new(), // Will be set for universal binaries.
+            ticket: None,       // Will be set if a ticket is stapled.
+            resources: None,    // Will be set with `--verify-resources`.
         },
         executable_path,
     )
@@ -317,62 +386,76 @@ fn format_output_human(info: &SignatureInfo, color: ColorConfig) -> String {
 
     // Status indicator - colored.
     let status_text = if info.is_valid && info.is_notarized {
-        "Valid & Notarized"
+        tr(Msg::StatusValidNotarized)
     } else if info.is_valid {
-        "Valid"
+        tr(Msg::StatusValid)
     } else {
-        "Invalid"
+        tr(Msg::StatusInvalid)
     };
 
-    let status_display = if !color.enabled {
-        status_text.to_string()
-    } else {
+    let status_display = if color.enabled {
         let status_color = if info.is_valid {
             style.green()
         } else {
             style.red()
         };
         status_text.style(status_color).to_string()
+    } else {
+        status_text.to_string()
+    };
+
+    let format = match info.format {
+        AppFormat::Application => tr(Msg::Application),
+        AppFormat::Executable => tr(Msg::Executable),
+        AppFormat::Unknown => tr(Msg::Unknown),
     };
 
     // Modernized `codesign --display --verbose=4` format.
     // Header section.
     let _ = writeln!(output, "{}", info.path);
     if let Some(ref resolved) = info.resolved_path {
-        let _ = writeln!(output, "Resolved to:      {}", resolved);
+        let _ = writeln!(output, "{}{resolved}", label(Msg::ResolvedTo));
     }
-    let _ = writeln!(output, "Identifier:       {}", info.identifier);
-    let _ = writeln!(output, "Format:           {}", info.format);
+    let _ = writeln!(output, "{}{}", label(Msg::Identifier), info.identifier);
+    let _ = writeln!(output, "{}{format}", label(Msg::Format));
     let _ = writeln!(
         output,
-        "CodeDirectory:    {}",
-        if let Some(ref hashes) = info.hashes {
-            &hashes.code_directory
-        } else {
-            "N/A"
-        }
+        "{}{}",
+        label(Msg::CodeDirectory),
+        info.hashes.as_ref().map_or_else(
+            || tr(Msg::NotAvailable),
+            |hashes| hashes.code_directory.as_str()
+        )
     );
 
     // Status line.
-    let _ = writeln!(output, "Status:           {}", status_display);
+    let _ = writeln!(output, "{}{status_display}", label(Msg::Status));
 
     // Authority section (mimics `codesign`'s `Authority=` lines).
     if !info.authorities.is_empty() {
         for auth in &info.authorities {
-            let _ = writeln!(output, "Authority:        {}", auth);
+            let _ = writeln!(output, "{}{auth}", label(Msg::Authority));
         }
     }
 
     // Notarization status.
-    if info.is_notarized {
-        let _ = writeln!(output, "Notarization:     Stapled");
+    if let Some(notarization) = notarization_status(info, color) {
+        let _ = writeln!(output, "{}{notarization}", label(Msg::Notarization));
     }
 
     output.push('\n');
 
+    // Architectures section, for universal binaries.
+    format_architectures(&mut output, &info.slices, color);
+
+    // Resources section, with `--verify-resources`.
+    if let Some(ref report) = info.resources {
+        format_resources(&mut output, report, color);
+    }
+
     // Hashes section.
     if let Some(ref hashes) = info.hashes {
-        let _ = writeln!(output, "Hashes:");
+        let _ = writeln!(output, "{}:", tr(Msg::Hashes));
         let _ = writeln!(output, "  MD5:    {}", hashes.md5);
         let _ = writeln!(output, "  SHA1:   {}", hashes.sha1);
         let _ = writeln!(output, "  SHA256: {}", hashes.sha256);
@@ -382,7 +465,7 @@ fn format_output_human(info: &SignatureInfo, color: ColorConfig) -> String {
 
     // Entitlements section.
     if let Some(ref entitlements) = info.entitlements {
-        let _ = writeln!(output, "Entitlements:");
+        let _ = writeln!(output, "{}:", tr(Msg::Entitlements));
         output.push_str(entitlements);
         output.push('\n');
     }
@@ -390,6 +473,148 @@ fn format_output_human(info: &SignatureInfo, color: ColorConfig) -> String {
     output
 }
 
+/// Lists each slice of a universal binary with its status, and warns when
+/// the slices disagree.
+fn format_architectures(output: &mut String, slices: &[SliceInfo], color: ColorConfig) {
+    if slices.is_empty() {
+        return;
+    }
+    let style = ColorConfig::style();
+    let _ = writeln!(output, "{}:", tr(Msg::Architectures));
+    let status_width = tr(Msg::StatusValid)
+        .chars()
+        .count()
+        .max(tr(Msg::StatusInvalid).chars().count());
+    for slice in slices {
+        let status = if slice.is_valid {
+            tr(Msg::StatusValid)
+        } else {
+            tr(Msg::StatusInvalid)
+        };
+        let status = if color.enabled {
+            let status_color = if slice.is_valid {
+                style.green()
+            } else {
+                style.red()
+            };
+            // Pad before styling so escape codes don't break alignment.
+            format!("{status:<status_width$}")
+                .style(status_color)
+                .to_string()
+        } else {
+            format!("{status:<status_width$}")
+        };
+        let _ = writeln!(
+            output,
+            "  {:<8} {status}  {}",
+            slice.arch,
+            if slice.code_directory.is_empty() {
+                tr(Msg::NotAvailable)
+            } else {
+                &slice.code_directory
+            }
+        );
+    }
+    for mismatch in fat::mismatches(slices) {
+        let warning = tr_with(Msg::Warning, &[("message", &mismatch)]);
+        if color.enabled {
+            let _ = writeln!(output, "  {}", warning.style(style.yellow()));
+        } else {
+            let _ = writeln!(output, "  {warning}");
+        }
+    }
+    output.push('\n');
+}
+
+/// Draws the deep validation result as a tree of the files that were
+/// added, modified or are missing, and the nested code that was checked.
+fn format_resources(output: &mut String, report: &ResourceReport, color: ColorConfig) {
+    let style = ColorConfig::style();
+    let _ = writeln!(output, "{}:", tr(Msg::Resources));
+
+    for problem in &report.problems {
+        if color.enabled {
+            let _ = writeln!(output, "  {}", problem.style(style.red()));
+        } else {
+            let _ = writeln!(output, "  {problem}");
+        }
+    }
+    if report.envelope_obsolete() {
+        let _ = writeln!(output, "  {}", tr(Msg::ResourcesResign));
+    }
+    if report.is_valid && report.changed().next().is_none() {
+        let intact = tr(Msg::ResourcesIntact);
+        if color.enabled {
+            let _ = writeln!(output, "  {}", intact.style(style.green()));
+        } else {
+            let _ = writeln!(output, "  {intact}");
+        }
+    }
+
+    let name = Path::new(&report.path).file_name().map_or_else(
+        || report.path.clone(),
+        |name| name.to_string_lossy().to_string(),
+    );
+    let _ = writeln!(output, "  {name}");
+    for line in resources::tree(report.entries.iter()) {
+        let Some(status) = line.status else {
+            let _ = writeln!(output, "  {}{}", line.prefix, line.name);
+            continue;
+        };
+        let (text, status_color) = match status {
+            ResourceStatus::Added => (tr(Msg::ResourceAdded), style.yellow()),
+            ResourceStatus::Modified => (tr(Msg::ResourceModified), style.yellow()),
+            ResourceStatus::Missing => (tr(Msg::ResourceMissing), style.red()),
+            ResourceStatus::Invalid => (tr(Msg::ResourceInvalid), style.red()),
+            ResourceStatus::Validated => (tr(Msg::ResourceValidated), style.green()),
+        };
+        let text = format!("[{text}]");
+        if color.enabled {
+            let _ = writeln!(
+                output,
+                "  {}{} {}",
+                line.prefix,
+                line.name,
+                text.style(status_color)
+            );
+        } else {
+            let _ = writeln!(output, "  {}{} {text}", line.prefix, line.name);
+        }
+    }
+    output.push('\n');
+}
+
+/// Describes the stapled ticket, or falls back to `codesign`'s "stapled"
+/// hint when no ticket file was found.
+fn notarization_status(info: &SignatureInfo, color: ColorConfig) -> Option<String> {
+    let Some(ref ticket) = info.ticket else {
+        return info.is_notarized.then(|| tr(Msg::Stapled).to_string());
+    };
+
+    if ticket.is_valid {
+        let date = ticket.date.as_ref().map_or_else(String::new, |date| {
+            tr_with(Msg::TicketSigned, &[("date", date)])
+        });
+        return Some(tr_with(Msg::StapledBound, &[("date", &date)]));
+    }
+
+    let warning = tr_with(
+        Msg::TicketInvalid,
+        &[(
+            "problem",
+            ticket
+                .problem
+                .as_deref()
+                .unwrap_or_else(|| tr(Msg::UnknownProblem)),
+        )],
+    );
+    if color.enabled {
+        Some(warning.style(ColorConfig::style().yellow()).to_string())
+    } else {
+        Some(warning)
+    }
+}
+
 fn format_output_plain(info: &SignatureInfo) -> String {
     let mut output = String::new();
 
@@ -406,7 +631,7 @@ fn format_output_plain(info: &SignatureInfo) -> String {
     let _ = writeln!(output, "name\t{name}", name = info.name);
     let _ = writeln!(output, "path\t{path}", path = info.path);
     if let Some(ref resolved) = info.resolved_path {
-        let _ = writeln!(output, "resolved_path\t{resolved}", resolved = resolved);
+        let _ = writeln!(output, "resolved_path\t{resolved}");
     }
     let _ = writeln!(output, "type\t{format}", format = info.format);
     let _ = writeln!(
@@ -437,6 +662,48 @@ fn format_output_plain(info: &SignatureInfo) -> String {
         let _ = writeln!(output, "authority_{i}\t{auth}");
     }
 
+    if let Some(ref ticket) = info.ticket {
+        let _ = writeln!(output, "ticket_path\t{}", ticket.path);
+        let _ = writeln!(output, "ticket_is_valid\t{}", ticket.is_valid);
+        let _ = writeln!(output, "ticket_cdhash_bound\t{}", ticket.cdhash_bound);
+        if let Some(ref date) = ticket.date {
+            let _ = writeln!(output, "ticket_date\t{date}");
+        }
+        if let Some(ref problem) = ticket.problem {
+            let _ = writeln!(output, "ticket_problem\t{problem}");
+        }
+    }
+
+    for slice in &info.slices {
+        let arch = &slice.arch;
+        let _ = writeln!(output, "slice_{arch}_is_valid\t{}", slice.is_valid);
+        let _ = writeln!(
+            output,
+            "slice_{arch}_code_directory_hash\t{}",
+            slice.code_directory
+        );
+    }
+    for mismatch in fat::mismatches(&info.slices) {
+        let _ = writeln!(output, "slice_mismatch\t{mismatch}");
+    }
+
+    if let Some(ref report) = info.resources {
+        let _ = writeln!(output, "resources_is_valid\t{}", report.is_valid);
+        for problem in &report.problems {
+            let _ = writeln!(output, "resources_problem\t{problem}");
+        }
+        for entry in &report.entries {
+            let status = match entry.status {
+                ResourceStatus::Added => "added",
+                ResourceStatus::Modified => "modified",
+                ResourceStatus::Missing => "missing",
+                ResourceStatus::Invalid => "invalid",
+                ResourceStatus::Validated => "validated",
+            };
+            let _ = writeln!(output, "resource_{status}\t{}", entry.path);
+        }
+    }
+
     if let Some(ref entitlements) = info.entitlements {
         // For plain format, output entitlements as a single line.
         let entitlements_clean = entitlements.replace(['\n', '\t'], " ");
@@ -703,19 +970,20 @@ fn format_entitlements_simple(plist: &str) -> String {
 /// * `color` - Color configuration
 /// * `quiet` - Whether to suppress non-essential output
 /// * `debug` - Whether to show debug information
+/// * `use_cache` - Whether to reuse and store results in the on-disk cache
 ///
 /// # Errors
 ///
 /// Returns an `io::Error` if any of the external tooling invocations fail.
 fn inspect_signature(
     path: &str,
-    format: OutputFormat,
+    args: &Args,
     color: ColorConfig,
-    quiet: bool,
-    debug: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if !quiet {
-        eprintln!("Inspecting signature...");
+    let debug = args.debug;
+    let use_cache = !args.no_cache;
+    if !args.quiet {
+        eprintln!("{}", tr(Msg::Inspecting));
     }
 
     // Resolve symlinks to get the actual file path.
@@ -741,15 +1009,19 @@ fn inspect_signature(
     } else {
         let stderr = String::from_utf8_lossy(&codesign_out.stderr);
         let error_msg = if stderr.contains("not signed") {
-            format!(
-                "The file '{path}' is not code signed. This tool only works with signed macOS applications and executables."
-            )
+            tr_with(Msg::NotSigned, &[("path", path)])
         } else if stderr.contains("No such file") {
-            format!("The file '{path}' doesn't exist or can't be accessed.")
+            tr_with(Msg::NotAccessible, &[("path", path)])
         } else {
-            format!(
-                "codesign failed: {}",
-                stderr.lines().next().unwrap_or("Unknown error")
+            tr_with(
+                Msg::CodesignFailed,
+                &[(
+                    "reason",
+                    stderr
+                        .lines()
+                        .next()
+                        .unwrap_or_else(|| tr(Msg::UnknownError)),
+                )],
             )
         };
 
@@ -757,23 +1029,9 @@ fn inspect_signature(
         eprintln!();
         print_error_message(&error_msg, color);
         eprintln!();
-        print_suggestion(
-            "Make sure the file is a signed macOS application (.app) or executable binary.",
-            color,
-        );
+        print_suggestion(tr(Msg::SuggestSignedBinary), color);
         eprintln!();
-        if !color.enabled {
-            eprintln!(
-                "Most important: The file must be a signed macOS binary to inspect its signature."
-            );
-        } else {
-            eprintln!(
-                "{}",
-                "Most important: The file must be a signed macOS binary to inspect its signature."
-                    .red()
-                    .bold()
-            );
-        }
+        print_most_important(tr(Msg::ImportantSignedBinary), color);
         return Err(Box::new(io::Error::other("codesign failed")));
     }
 
@@ -784,6 +1042,98 @@ fn inspect_signature(
         info.resolved_path = Some(actual_path.clone());
     }
 
+    // Hashes use the executable for app bundles, otherwise the resolved path.
+    let hash_path = executable_path.unwrap_or_else(|| actual_path.clone());
+
+    // The cache is keyed by the resolved path; a hit requires the same size,
+    // mtime and CDHash, which `codesign -dvvv` above already gave us cheaply.
+    let fingerprint = Fingerprint::new(
+        Path::new(&hash_path),
+        &code_directory_hash(&codesign_stderr),
+    );
+    let mut cache = if use_cache {
+        ResultCache::load()
+    } else {
+        ResultCache::default()
+    };
+
+    if use_cache
+        && let Some(ref fingerprint) = fingerprint
+        && let Some(mut cached) = cache.get(&actual_path, fingerprint)
+    {
+        if debug {
+            eprintln!("Debug: Using cached result for {actual_path}");
+        }
+        cached.path = info.path;
+        cached.resolved_path = info.resolved_path;
+        if args.verify_resources {
+            cached.resources = Some(verify_resources(check_path, color, debug)?);
+        }
+        return print_signature_info(&cached, args.format, color, debug);
+    }
+
+    collect_signature_details(
+        &mut info,
+        check_path,
+        &codesign_stderr,
+        &hash_path,
+        color,
+        debug,
+    )?;
+
+    if use_cache && let Some(fingerprint) = fingerprint {
+        cache.insert(actual_path.clone(), fingerprint, &info);
+        if let Err(e) = cache.save()
+            && debug
+        {
+            eprintln!("Debug: Failed to write cache: {e}");
+        }
+    }
+
+    if args.verify_resources {
+        info.resources = Some(verify_resources(check_path, color, debug)?);
+    }
+
+    print_signature_info(&info, args.format, color, debug)
+}
+
+/// Runs the deep resource validation for `--verify-resources`.
+fn verify_resources(
+    check_path: &str,
+    color: ColorConfig,
+    debug: bool,
+) -> io::Result<ResourceReport> {
+    resources::verify(check_path).inspect_err(|e| {
+        print_command_error("codesign", e, check_path, color, debug);
+    })
+}
+
+/// Extracts the code directory hash from `codesign -dvvv` output, preferring
+/// the full SHA-256 candidate hash over the short `CDHash` line.
+fn code_directory_hash(codesign_stderr: &str) -> String {
+    let mut code_directory = String::new();
+    for line in codesign_stderr.lines() {
+        if line.starts_with("CandidateCDHashFull sha256=") {
+            return line.split('=').nth(1).unwrap_or("").to_uppercase();
+        }
+        if line.starts_with("CDHash=") && code_directory.is_empty() {
+            // Fallback to short CDHash if full is not available.
+            code_directory = line.split('=').nth(1).unwrap_or("").to_uppercase();
+        }
+    }
+    code_directory
+}
+
+/// Fills in validity, notarization, hashes and entitlements, which are the
+/// expensive parts of an inspection.
+fn collect_signature_details(
+    info: &mut SignatureInfo,
+    check_path: &str,
+    codesign_stderr: &str,
+    hash_path: &str,
+    color: ColorConfig,
+    debug: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Check signature validity using `codesign -vv`.
     let (is_valid, source) = check_signature_validity(check_path).inspect_err(|e| {
         print_command_error("codesign", e, check_path, color, debug);
@@ -814,33 +1164,68 @@ fn inspect_signature(
         }
     }
 
-    // Get file hashes - use executable path for app bundles, otherwise use the resolved path.
-    let hash_path: &str = executable_path
-        .as_ref()
-        .map_or(check_path, |exec_path| exec_path.as_str());
-    if let Ok(mut hash_info) = get_file_hashes(hash_path) {
-        // Extract code directory hash from `codesign` output.
-        for line in codesign_stderr.lines() {
-            if line.starts_with("CandidateCDHashFull sha256=") {
-                hash_info.code_directory = line.split('=').nth(1).unwrap_or("").to_uppercase();
-                break;
-            }
-            if line.starts_with("CDHash=") && hash_info.code_directory.is_empty() {
-                // Fallback to short CDHash if full is not available.
-                hash_info.code_directory = line.split('=').nth(1).unwrap_or("").to_uppercase();
+    // A stapled ticket is checked directly rather than trusting the
+    // "stapled" hint alone; the hint is kept for formats without a bundle.
+    let bundle_path = if Path::new(check_path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
+    {
+        Some(check_path.to_string())
+    } else {
+        find_app_bundle(check_path)
+    };
+    if let Some(ref bundle_path) = bundle_path {
+        info.ticket = ticket::inspect(Path::new(bundle_path), codesign_stderr);
+        if let Some(ref ticket) = info.ticket {
+            info.is_notarized = source.contains("Notarized") || ticket.is_valid;
+            if debug && !ticket.is_valid {
+                eprintln!("Debug: Stapled ticket at {} failed validation", ticket.path);
             }
         }
+    }
+
+    if let Ok(mut hash_info) = get_file_hashes(hash_path) {
+        hash_info.code_directory = code_directory_hash(codesign_stderr);
         info.hashes = Some(hash_info);
     }
 
     // Get entitlements - this is optional, so we don't fail if it errors.
     info.entitlements = get_entitlements(check_path).unwrap_or(None);
 
+    // For universal binaries, check every slice rather than only the one
+    // `codesign` picks for this machine.
+    let archs = fat::architectures(Path::new(hash_path)).unwrap_or_default();
+    if archs.len() > 1 {
+        info.slices = archs
+            .iter()
+            .filter_map(|arch| {
+                fat::inspect_slice(check_path, arch)
+                    .inspect_err(|e| {
+                        if debug {
+                            eprintln!("Debug: Failed to inspect {arch} slice: {e}");
+                        }
+                    })
+                    .ok()
+            })
+            .collect();
+    }
+
+    Ok(())
+}
+
+/// Formats the inspection result and writes it to stdout, paging long
+/// human-readable output when attached to a TTY.
+fn print_signature_info(
+    info: &SignatureInfo,
+    format: OutputFormat,
+    color: ColorConfig,
+    debug: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     // Format and output based on format.
     let output = match format {
-        OutputFormat::Human => format_output_human(&info, color),
-        OutputFormat::Plain => format_output_plain(&info),
-        OutputFormat::Json => format_output_json(&info),
+        OutputFormat::Human => format_output_human(info, color),
+        OutputFormat::Plain => format_output_plain(info),
+        OutputFormat::Json => format_output_json(info),
     };
 
     // Use pager for human-readable output if it's long and we're in a TTY.
@@ -873,10 +1258,8 @@ fn find_app_bundle(path: &str) -> Option<String> {
     // Walk up the directory tree to find `.app` bundle.
     while let Some(parent) = current.parent() {
         if parent
-            .file_name()
-            .and_then(|n| n.to_str())
-            .map(|n| n.ends_with(".app"))
-            .unwrap_or(false)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("app"))
         {
             return Some(parent.to_string_lossy().to_string());
         }
@@ -901,10 +1284,10 @@ fn resolve_symlink(path: &Path) -> io::Result<PathBuf> {
         }
         current = current.read_link()?;
         // If the symlink is relative, resolve it relative to the parent.
-        if current.is_relative() {
-            if let Some(parent) = path.parent() {
-                current = parent.join(&current);
-            }
+        if current.is_relative()
+            && let Some(parent) = path.parent()
+        {
+            current = parent.join(&current);
         }
     }
 
@@ -931,10 +1314,10 @@ fn check_dependencies() -> Result<(), Vec<String>> {
 }
 
 fn print_error_header(color: ColorConfig) {
-    let header = if !color.enabled {
-        "Error".to_string()
+    let header = if color.enabled {
+        tr(Msg::Error).red().bold().to_string()
     } else {
-        "Error".red().bold().to_string()
+        tr(Msg::Error).to_string()
     };
     eprintln!("{header}");
 }
@@ -955,97 +1338,73 @@ fn print_suggestion(suggestion: &str, color: ColorConfig) {
     eprintln!("{}", suggestion.bright_blue());
 }
 
+/// Prints the closing "Most important" line of an error report.
+fn print_most_important(message: &str, color: ColorConfig) {
+    if !color.enabled {
+        eprintln!("{message}");
+        return;
+    }
+    eprintln!("{}", message.red().bold());
+}
+
 fn print_path_error(path: &str, color: ColorConfig) {
     print_error_header(color);
     eprintln!();
-    eprintln!("Can't find the file or directory:");
+    eprintln!("{}", tr(Msg::PathNotFound));
     eprintln!("  {path}");
     eprintln!();
 
     // Check if it's a permission issue.
     let path_obj = Path::new(path);
     let Some(parent) = path_obj.parent() else {
-        print_suggestion("Make sure the path is correct and the file exists.", color);
+        print_suggestion(tr(Msg::SuggestCheckPath), color);
         eprintln!();
-        if !color.enabled {
-            eprintln!("Most important: Check that the path is correct and the file exists.");
-        } else {
-            eprintln!(
-                "{}",
-                "Most important: Check that the path is correct and the file exists."
-                    .red()
-                    .bold()
-            );
-        }
+        print_most_important(tr(Msg::ImportantCheckPath), color);
         return;
     };
 
     if !parent.exists() {
         print_suggestion(
-            &format!(
-                "The directory '{}' doesn't exist. Make sure the path is correct.",
-                parent.display()
+            &tr_with(
+                Msg::SuggestMissingDirectory,
+                &[("directory", &parent.display().to_string())],
             ),
             color,
         );
         eprintln!();
-        if !color.enabled {
-            eprintln!("Most important: Check that the path is correct and the file exists.");
-        } else {
-            eprintln!(
-                "{}",
-                "Most important: Check that the path is correct and the file exists."
-                    .red()
-                    .bold()
-            );
-        }
+        print_most_important(tr(Msg::ImportantCheckPath), color);
         return;
     }
 
     print_suggestion(
-        &format!(
-            "The path exists but the file '{}' doesn't. Check the spelling and try again.",
-            path_obj.file_name().and_then(|n| n.to_str()).unwrap_or("")
+        &tr_with(
+            Msg::SuggestMissingFile,
+            &[(
+                "file",
+                path_obj.file_name().and_then(|n| n.to_str()).unwrap_or(""),
+            )],
         ),
         color,
     );
 
     eprintln!();
-    if color.enabled {
-        eprintln!(
-            "{}",
-            "Most important: Check that the path is correct and the file exists."
-                .red()
-                .bold()
-        );
-    } else {
-        eprintln!("Most important: Check that the path is correct and the file exists.");
-    }
+    print_most_important(tr(Msg::ImportantCheckPath), color);
 }
 
 fn print_dependency_error(missing: &[String], color: ColorConfig) {
     print_error_header(color);
     eprintln!();
-    eprintln!("Missing required tools:");
+    eprintln!("{}", tr(Msg::MissingTools));
     for tool in missing {
         eprintln!("  • {tool}");
     }
     eprintln!();
 
-    print_suggestion("Install Xcode Command Line Tools by running:", color);
+    print_suggestion(tr(Msg::SuggestInstallXcode), color);
     eprintln!("  xcode-select --install");
     eprintln!();
 
-    if !color.enabled {
-        eprintln!("Most important: Install Xcode Command Line Tools to use this tool.");
-        return;
-    }
-    eprintln!(
-        "{}",
-        "Most important: Install Xcode Command Line Tools to use this tool."
-            .red()
-            .bold()
-    );
+    print_most_important(tr(Msg::ImportantInstallXcode), color);
 }
 
 fn print_command_error(
@@ -1057,23 +1416,15 @@ fn print_command_error(
 ) {
     print_error_header(color);
     eprintln!();
-    eprintln!("Failed to run '{command}' on:");
+    eprintln!("{}", tr_with(Msg::CommandFailed, &[("command", command)]));
     eprintln!("  {path}");
     eprintln!();
 
     // Try to provide helpful context based on error kind.
     let error_msg = match error.kind() {
-        io::ErrorKind::NotFound => {
-            format!("The '{command}' command was not found.")
-        }
-        io::ErrorKind::PermissionDenied => {
-            format!(
-                "Permission denied. You might need to make the file readable by running:\n  chmod +r \"{path}\""
-            )
-        }
-        _ => {
-            format!("Error: {error}")
-        }
+        io::ErrorKind::NotFound => tr_with(Msg::CommandNotFound, &[("command", command)]),
+        io::ErrorKind::PermissionDenied => tr_with(Msg::PermissionDenied, &[("path", path)]),
+        _ => tr_with(Msg::OtherError, &[("error", &error.to_string())]),
     };
 
     print_error_message(&error_msg, color);
@@ -1081,10 +1432,7 @@ fn print_command_error(
 
     // Check if it's an unsigned file.
     if command == "codesign" {
-        print_suggestion(
-            "The file might not be a signed macOS application or executable.",
-            color,
-        );
+        print_suggestion(tr(Msg::SuggestUnsigned), color);
         eprintln!();
     }
 
@@ -1097,19 +1445,9 @@ fn print_command_error(
         eprintln!();
     }
 
-    if !color.enabled {
-        eprintln!(
-            "Most important: Make sure '{command}' can access the file and it's a valid macOS binary."
-        );
-        return;
-    }
-    eprintln!(
-        "{}",
-        format!(
-            "Most important: Make sure '{command}' can access the file and it's a valid macOS binary."
-        )
-        .red()
-        .bold()
+    print_most_important(
+        &tr_with(Msg::ImportantCommandAccess, &[("command", command)]),
+        color,
     );
 }
 
@@ -1121,13 +1459,11 @@ fn print_unexpected_error(
 ) {
     print_error_header(color);
     eprintln!();
-    eprintln!("An unexpected error occurred:");
+    eprintln!("{}", tr(Msg::UnexpectedError));
     eprintln!("  {context}");
     eprintln!();
 
-    if !debug {
-        eprintln!("Run with --debug to see detailed error information.");
-    } else {
+    if debug {
         eprintln!("Debug information:");
         eprintln!("  Error: {error}");
         let mut source = error.source();
@@ -1137,60 +1473,44 @@ fn print_unexpected_error(
             eprintln!("  Caused by ({depth}): {err}");
             source = err.source();
         }
+    } else {
+        eprintln!("{}", tr(Msg::RunWithDebug));
     }
     eprintln!();
 
     // Bug report information.
-    eprintln!("This looks like a bug. Please report it:");
+    eprintln!("{}", tr(Msg::ReportBug));
     eprintln!("  https://github.com/anhkhoakz/some-rust-scripts/issues/new");
     eprintln!();
-    eprintln!("Include the following information:");
-    eprintln!("  • The command you ran");
-    eprintln!("  • The error message above");
+    eprintln!("{}", tr(Msg::IncludeInfo));
+    eprintln!("  • {}", tr(Msg::InfoCommand));
+    eprintln!("  • {}", tr(Msg::InfoMessage));
     if !debug {
-        eprintln!("  • Output from running with --debug flag");
+        eprintln!("  • {}", tr(Msg::InfoDebugOutput));
     }
-    eprintln!("  • Your macOS version");
+    eprintln!("  • {}", tr(Msg::InfoMacosVersion));
     eprintln!();
 
-    if !color.enabled {
-        eprintln!("Most important: This is a bug. Please report it with the information above.");
-        return;
-    }
-    eprintln!(
-        "{}",
-        "Most important: This is a bug. Please report it with the information above."
-            .red()
-            .bold()
-    );
+    print_most_important(tr(Msg::ImportantBug), color);
 }
 
 fn main() -> ExitCode {
     if !cfg!(target_os = "macos") {
+        let lang = Args::try_parse().ok().and_then(|args| args.lang);
+        i18n::set_lang(lang.unwrap_or_else(Lang::from_env));
         let color = ColorConfig::new();
         print_error_header(color);
         eprintln!();
-        eprintln!("This tool only works on macOS.");
+        eprintln!("{}", tr(Msg::MacosOnly));
         eprintln!();
-        print_suggestion(
-            "Run this tool on a macOS system to inspect code signatures.",
-            color,
-        );
+        print_suggestion(tr(Msg::SuggestMacos), color);
         eprintln!();
-        if !color.enabled {
-            eprintln!("Most important: This tool requires macOS to function.");
-        } else {
-            eprintln!(
-                "{}",
-                "Most important: This tool requires macOS to function."
-                    .red()
-                    .bold()
-            );
-        }
+        print_most_important(tr(Msg::ImportantMacos), color);
         return ExitCode::FAILURE;
     }
 
     let args = Args::parse();
+    i18n::set_lang(args.lang.unwrap_or_else(Lang::from_env));
 
     // Determine color configuration.
     let mut color = ColorConfig::new();
@@ -1198,9 +1518,36 @@ fn main() -> ExitCode {
         color.enabled = false;
     }
 
-    let path = Path::new(&args.path);
+    if matches!(
+        args.command,
+        Some(Commands::Cache {
+            action: CacheCommand::Clear
+        })
+    ) {
+        return match ResultCache::clear() {
+            Ok(true) => {
+                println!("{}", tr(Msg::CacheCleared));
+                ExitCode::SUCCESS
+            }
+            Ok(false) => {
+                println!("{}", tr(Msg::CacheEmpty));
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                print_unexpected_error(&e, tr(Msg::WhileClearingCache), color, args.debug);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    // `--path` is required unless a subcommand was given.
+    let Some(ref target) = args.path else {
+        return ExitCode::FAILURE;
+    };
+
+    let path = Path::new(target);
     if !path.exists() {
-        print_path_error(&args.path, color);
+        print_path_error(target, color);
         return ExitCode::FAILURE;
     }
 
@@ -1209,13 +1556,13 @@ fn main() -> ExitCode {
         return ExitCode::FAILURE;
     }
 
-    if let Err(e) = inspect_signature(&args.path, args.format, color, args.quiet, args.debug) {
+    if let Err(e) = inspect_signature(target, &args, color) {
         // Error messages are already printed by `inspect_signature` for most cases.
         // For truly unexpected errors, print additional debug info.
         let error_str = e.to_string();
         // Only print unexpected error if it's not one we've already handled.
         if !error_str.contains("codesign failed") && args.debug {
-            print_unexpected_error(e.as_ref(), "while inspecting signature", color, args.debug);
+            print_unexpected_error(e.as_ref(), tr(Msg::WhileInspecting), color, args.debug);
         }
         return ExitCode::FAILURE;
     }
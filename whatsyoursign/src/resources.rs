@@ -0,0 +1,285 @@
+//! Deep validation of the sealed resources of a bundle.
+//!
+//! `codesign --verify --deep --strict` reports each resource that differs
+//! from the seal on its own line (`file added: ...`, `file modified: ...`,
+//! `file missing: ...`) and, at `--verbose=4`, each nested code item it
+//! validated. Those lines are collected here and laid out as a tree.
+
+use serde::{
+    Deserialize,
+    Serialize, //
+};
+use std::collections::BTreeMap;
+use std::io;
+use std::process::Command;
+
+/// Summary lines that `codesign` prints for a bundle that passed.
+const SUCCESS_MESSAGES: &[&str] = &["valid on disk", "satisfies its Designated Requirement"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceStatus {
+    Added,
+    Modified,
+    Missing,
+    Invalid, // Nested code that failed validation.
+    Validated,
+}
+
+/// `codesign` output lines that name a single file or nested code item.
+const LINE_PREFIXES: &[(&str, ResourceStatus)] = &[
+    ("file added: ", ResourceStatus::Added),
+    ("file modified: ", ResourceStatus::Modified),
+    ("file missing: ", ResourceStatus::Missing),
+    ("In subcomponent: ", ResourceStatus::Invalid),
+    ("--validated:", ResourceStatus::Validated),
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResourceEntry {
+    pub path: String, // Relative to the bundle.
+    pub status: ResourceStatus,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ResourceReport {
+    pub path: String,
+    pub is_valid: bool,
+    pub problems: Vec<String>, // Failure messages `codesign` gave for the bundle.
+    pub entries: Vec<ResourceEntry>,
+}
+
+impl ResourceReport {
+    /// Whether the bundle was sealed by an older `codesign` whose resource
+    /// rules are no longer accepted.
+    pub fn envelope_obsolete(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| problem.contains("resource envelope is obsolete"))
+    }
+
+    /// Entries that differ from the seal, leaving out validated nested code.
+    pub fn changed(&self) -> impl Iterator<Item = &ResourceEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status != ResourceStatus::Validated)
+    }
+}
+
+/// Runs `codesign --verify --deep --strict --verbose=4` on `path`.
+pub fn verify(path: &str) -> io::Result<ResourceReport> {
+    let output = Command::new("codesign")
+        .args(["--verify", "--deep", "--strict", "--verbose=4", path])
+        .output()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(parse(path, &stderr, output.status.success()))
+}
+
+fn parse(path: &str, stderr: &str, is_valid: bool) -> ResourceReport {
+    let mut problems = Vec::new();
+    let mut entries = Vec::new();
+    let summary_prefix = format!("{path}: ");
+
+    for line in stderr.lines().map(str::trim) {
+        let entry = LINE_PREFIXES
+            .iter()
+            .find_map(|&(prefix, status)| line.strip_prefix(prefix).map(|file| (file, status)));
+
+        if let Some((file, status)) = entry {
+            entries.push(ResourceEntry {
+                path: relative_path(path, file),
+                status,
+            });
+        } else if let Some(message) = line.strip_prefix(&summary_prefix)
+            && !SUCCESS_MESSAGES.contains(&message)
+        {
+            problems.push(message.to_string());
+        }
+    }
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries.dedup_by(|a, b| a.path == b.path && a.status == b.status);
+
+    ResourceReport {
+        path: path.to_string(),
+        is_valid,
+        problems,
+        entries,
+    }
+}
+
+/// Strips the bundle prefix and the `/.` that `codesign` appends to nested
+/// bundle paths.
+fn relative_path(bundle: &str, file: &str) -> String {
+    let file = file.trim_end_matches("/.");
+    let bundle = bundle.trim_end_matches('/');
+    file.strip_prefix(bundle)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(file)
+        .to_string()
+}
+
+/// One line of a rendered tree: the branch drawing, the path component and
+/// the status of the entry ending there, if any.
+pub struct TreeLine {
+    pub prefix: String,
+    pub name: String,
+    pub status: Option<ResourceStatus>,
+}
+
+#[derive(Default)]
+struct Node {
+    status: Option<ResourceStatus>,
+    children: BTreeMap<String, Self>,
+}
+
+/// Lays the entries out as a tree, sorted by name at each level.
+pub fn tree<'a>(entries: impl Iterator<Item = &'a ResourceEntry>) -> Vec<TreeLine> {
+    let mut root = Node::default();
+    for entry in entries {
+        let node = entry
+            .path
+            .split('/')
+            .filter(|component| !component.is_empty())
+            .fold(&mut root, |node, component| {
+                node.children.entry(component.to_string()).or_default()
+            });
+        node.status = Some(entry.status);
+    }
+
+    let mut lines = Vec::new();
+    push_children(&root, "", &mut lines);
+    lines
+}
+
+fn push_children(node: &Node, indent: &str, lines: &mut Vec<TreeLine>) {
+    let count = node.children.len();
+    for (i, (name, child)) in node.children.iter().enumerate() {
+        let last = i + 1 == count;
+        lines.push(TreeLine {
+            prefix: format!("{indent}{}", if last { "└── " } else { "├── " }),
+            name: name.clone(),
+            status: child.status,
+        });
+        let child_indent = format!("{indent}{}", if last { "    " } else { "│   " });
+        push_children(child, &child_indent, lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLE: &str = "/Applications/Foo.app";
+
+    /// `codesign --verify --deep --strict --verbose=4` on a bundle with a
+    /// tampered nested framework and changed top-level resources.
+    const TAMPERED: &str = "\
+--prepared:/Applications/Foo.app/Contents/Frameworks/Bar.framework/Versions/Current/.
+--validated:/Applications/Foo.app/Contents/Frameworks/Baz.framework/Versions/Current/.
+/Applications/Foo.app: a sealed resource is missing or invalid
+In subcomponent: /Applications/Foo.app/Contents/Frameworks/Bar.framework
+file modified: /Applications/Foo.app/Contents/Frameworks/Bar.framework/Versions/A/Resources/Info.plist
+file added: /Applications/Foo.app/Contents/Resources/extra.txt
+file missing: /Applications/Foo.app/Contents/Resources/icon.icns
+file modified: /Applications/Foo.app/Contents/Resources/en.lproj/Localizable.strings
+";
+
+    /// The same command on an untouched bundle.
+    const VALID: &str = "\
+--prepared:/Applications/Foo.app/Contents/Frameworks/Baz.framework/Versions/Current/.
+--validated:/Applications/Foo.app/Contents/Frameworks/Baz.framework/Versions/Current/.
+/Applications/Foo.app: valid on disk
+/Applications/Foo.app: satisfies its Designated Requirement
+";
+
+    fn entries(report: &ResourceReport) -> Vec<(&str, ResourceStatus)> {
+        report
+            .entries
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.status))
+            .collect()
+    }
+
+    #[test]
+    fn parses_changed_resources() {
+        let report = parse(BUNDLE, TAMPERED, false);
+        assert_eq!(report.problems, ["a sealed resource is missing or invalid"]);
+        assert!(!report.envelope_obsolete());
+        assert_eq!(
+            entries(&report),
+            [
+                ("Contents/Frameworks/Bar.framework", ResourceStatus::Invalid),
+                (
+                    "Contents/Frameworks/Bar.framework/Versions/A/Resources/Info.plist",
+                    ResourceStatus::Modified
+                ),
+                (
+                    "Contents/Frameworks/Baz.framework/Versions/Current",
+                    ResourceStatus::Validated
+                ),
+                (
+                    "Contents/Resources/en.lproj/Localizable.strings",
+                    ResourceStatus::Modified
+                ),
+                ("Contents/Resources/extra.txt", ResourceStatus::Added),
+                ("Contents/Resources/icon.icns", ResourceStatus::Missing),
+            ]
+        );
+        assert_eq!(report.changed().count(), 5);
+    }
+
+    #[test]
+    fn success_summary_is_not_a_problem() {
+        let report = parse(&format!("{BUNDLE}/"), VALID, true);
+        assert!(report.is_valid);
+        assert!(report.problems.is_empty());
+        assert_eq!(
+            entries(&report),
+            [(
+                "Contents/Frameworks/Baz.framework/Versions/Current",
+                ResourceStatus::Validated
+            )]
+        );
+        assert_eq!(report.changed().count(), 0);
+    }
+
+    #[test]
+    fn detects_obsolete_envelope() {
+        let stderr = format!("{BUNDLE}: resource envelope is obsolete (version 1 signature)\n");
+        assert!(parse(BUNDLE, &stderr, false).envelope_obsolete());
+    }
+
+    #[test]
+    fn draws_tree_branches() {
+        let report = parse(BUNDLE, TAMPERED, false);
+        let lines: Vec<String> = tree(report.changed())
+            .iter()
+            .map(|line| format!("{}{}", line.prefix, line.name))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "└── Contents",
+                "    ├── Frameworks",
+                "    │   └── Bar.framework",
+                "    │       └── Versions",
+                "    │           └── A",
+                "    │               └── Resources",
+                "    │                   └── Info.plist",
+                "    └── Resources",
+                "        ├── en.lproj",
+                "        │   └── Localizable.strings",
+                "        ├── extra.txt",
+                "        └── icon.icns",
+            ]
+        );
+        let statuses: Vec<Option<ResourceStatus>> = tree(report.changed())
+            .iter()
+            .map(|line| line.status)
+            .collect();
+        assert_eq!(statuses[2], Some(ResourceStatus::Invalid));
+        assert_eq!(statuses[3], None);
+        assert_eq!(statuses[11], Some(ResourceStatus::Missing));
+    }
+}
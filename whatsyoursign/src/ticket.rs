@@ -0,0 +1,297 @@
+//! Local validation of stapled notarization tickets.
+//!
+//! `stapler` writes the ticket for an app bundle to `Contents/CodeResources`.
+//! The ticket is a binary blob starting with the `s8ch` magic that lists the
+//! truncated code directory hashes it covers and carries a CMS signature.
+//! Only its structure and hash binding are checked here; Apple's signature
+//! over the ticket is left to `spctl`.
+
+use serde::{
+    Deserialize,
+    Serialize, //
+};
+use std::fs;
+use std::path::Path;
+
+const TICKET_MAGIC: &[u8; 4] = b"s8ch";
+const TICKET_VERSION: u32 = 1;
+const TICKET_HEADER_LEN: usize = 8;
+/// Tickets bind the first 20 bytes of each code directory hash.
+const TRUNCATED_CDHASH_LEN: usize = 20;
+/// DER encoding of the PKCS #9 `signingTime` attribute OID (1.2.840.113549.1.9.5).
+const SIGNING_TIME_OID: &[u8] = &[
+    0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x05,
+];
+const DER_UTC_TIME: u8 = 0x17;
+const DER_GENERALIZED_TIME: u8 = 0x18;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StapledTicket {
+    pub path: String,
+    pub is_valid: bool,
+    pub problem: Option<String>, // Why the ticket failed validation.
+    pub cdhash_bound: bool,
+    pub date: Option<String>, // Signing time, when the ticket records one.
+}
+
+/// Looks for a stapled ticket in `bundle` and validates it against the
+/// code directory hashes `codesign` reported. Returns `None` when nothing is stapled.
+pub fn inspect(bundle: &Path, codesign_stderr: &str) -> Option<StapledTicket> {
+    let path = bundle.join("Contents").join("CodeResources");
+    let data = fs::read(&path).ok()?;
+    let path = path.to_string_lossy().to_string();
+
+    if let Err(problem) = check_header(&data) {
+        return Some(StapledTicket {
+            path,
+            is_valid: false,
+            problem: Some(problem),
+            cdhash_bound: false,
+            date: None,
+        });
+    }
+
+    let cdhashes = truncated_cdhashes(codesign_stderr);
+    let cdhash_bound = cdhashes.iter().any(|hash| {
+        data.windows(hash.len())
+            .any(|window| window == hash.as_slice())
+    });
+
+    Some(StapledTicket {
+        path,
+        is_valid: cdhash_bound,
+        problem: (!cdhash_bound)
+            .then(|| "ticket does not cover this code directory hash".to_string()),
+        cdhash_bound,
+        date: signing_time(&data),
+    })
+}
+
+fn check_header(data: &[u8]) -> Result<(), String> {
+    if data.len() <= TICKET_HEADER_LEN {
+        return Err("ticket is truncated".to_string());
+    }
+    if &data[..4] != TICKET_MAGIC {
+        return Err("file does not start with the ticket magic".to_string());
+    }
+    let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    if version != TICKET_VERSION {
+        return Err(format!("unsupported ticket version {version}"));
+    }
+    Ok(())
+}
+
+/// Collects every code directory hash `codesign -dvvv` printed, truncated to
+/// the length tickets store.
+fn truncated_cdhashes(codesign_stderr: &str) -> Vec<Vec<u8>> {
+    codesign_stderr
+        .lines()
+        .filter(|line| line.starts_with("CDHash=") || line.starts_with("CandidateCDHash"))
+        .filter_map(|line| line.split('=').nth(1))
+        .filter_map(decode_hex)
+        .filter(|bytes| bytes.len() >= TRUNCATED_CDHASH_LEN)
+        .map(|mut bytes| {
+            bytes.truncate(TRUNCATED_CDHASH_LEN);
+            bytes
+        })
+        .collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim();
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Reads the CMS `signingTime` attribute, which is encoded as the OID
+/// followed by a SET holding a single `UTCTime` or `GeneralizedTime`.
+fn signing_time(data: &[u8]) -> Option<String> {
+    let start = data
+        .windows(SIGNING_TIME_OID.len())
+        .position(|window| window == SIGNING_TIME_OID)?
+        + SIGNING_TIME_OID.len();
+    // Skip the SET tag and length.
+    let time = data.get(start + 2..)?;
+    let (&tag, rest) = time.split_first()?;
+    let (&len, rest) = rest.split_first()?;
+    let text = std::str::from_utf8(rest.get(..usize::from(len))?).ok()?;
+    if !text.is_ascii() {
+        return None;
+    }
+
+    let full = match tag {
+        // Two-digit years 50-99 are 19xx per RFC 5280.
+        DER_UTC_TIME if text.len() == 13 => {
+            let century = if text[..2] >= *"50" { "19" } else { "20" };
+            format!("{century}{text}")
+        }
+        DER_GENERALIZED_TIME if text.len() == 15 => text.to_string(),
+        _ => return None,
+    };
+    if !full[..14].bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!(
+        "{}-{}-{} {}:{}:{} UTC",
+        &full[..4],
+        &full[4..6],
+        &full[6..8],
+        &full[8..10],
+        &full[10..12],
+        &full[12..14]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(version: u32, body: &[u8]) -> Vec<u8> {
+        let mut data = TICKET_MAGIC.to_vec();
+        data.extend_from_slice(&version.to_le_bytes());
+        data.extend_from_slice(body);
+        data
+    }
+
+    /// A `signingTime` attribute holding `text` under the DER `tag`.
+    fn signing_time_attribute(tag: u8, text: &str) -> Vec<u8> {
+        let len = u8::try_from(text.len()).unwrap();
+        let mut data = SIGNING_TIME_OID.to_vec();
+        data.extend_from_slice(&[0x31, len + 2, tag, len]);
+        data.extend_from_slice(text.as_bytes());
+        data
+    }
+
+    #[test]
+    fn accepts_valid_header() {
+        assert_eq!(check_header(&ticket(TICKET_VERSION, &[0])), Ok(()));
+    }
+
+    #[test]
+    fn rejects_truncated_ticket() {
+        assert_eq!(
+            check_header(b"s8ch"),
+            Err("ticket is truncated".to_string())
+        );
+        assert!(check_header(&ticket(TICKET_VERSION, &[])).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_magic() {
+        let mut data = ticket(TICKET_VERSION, &[0]);
+        data[..4].copy_from_slice(b"<?xm");
+        assert_eq!(
+            check_header(&data),
+            Err("file does not start with the ticket magic".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        assert_eq!(
+            check_header(&ticket(2, &[0])),
+            Err("unsupported ticket version 2".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_utc_time_around_the_pivot() {
+        for (text, expected) in [
+            ("490101000000Z", "2049-01-01 00:00:00 UTC"),
+            ("500101000000Z", "1950-01-01 00:00:00 UTC"),
+            ("241231235959Z", "2024-12-31 23:59:59 UTC"),
+        ] {
+            let data = ticket(TICKET_VERSION, &signing_time_attribute(DER_UTC_TIME, text));
+            assert_eq!(signing_time(&data).as_deref(), Some(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn reads_generalized_time() {
+        let data = ticket(
+            TICKET_VERSION,
+            &signing_time_attribute(DER_GENERALIZED_TIME, "20510615083000Z"),
+        );
+        assert_eq!(
+            signing_time(&data).as_deref(),
+            Some("2051-06-15 08:30:00 UTC")
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_signing_time() {
+        assert_eq!(signing_time(&ticket(TICKET_VERSION, &[0])), None);
+        for (tag, text) in [
+            (DER_UTC_TIME, "2405011200Z"),
+            (DER_GENERALIZED_TIME, "240501120000Z"),
+            (DER_UTC_TIME, "24O501120000Z"),
+            (0x04, "240501120000Z"),
+        ] {
+            let data = ticket(TICKET_VERSION, &signing_time_attribute(tag, text));
+            assert_eq!(signing_time(&data), None, "{text}");
+        }
+        let mut data = ticket(
+            TICKET_VERSION,
+            &signing_time_attribute(DER_UTC_TIME, "240501120000Z"),
+        );
+        data.truncate(data.len() - 4);
+        assert_eq!(signing_time(&data), None);
+    }
+
+    #[test]
+    fn collects_truncated_cdhashes() {
+        let stderr = "\
+Executable=/Applications/Foo.app/Contents/MacOS/Foo
+CandidateCDHash sha1=00112233445566778899aabbccddeeff00112233
+CandidateCDHash sha256=ffeeddccbbaa99887766554433221100ffeeddccbbaa99887766554433221100
+Hash choices=sha1,sha256
+CDHash=ffeeddccbbaa99887766554433221100ffeeddccbbaa99887766554433221100
+";
+        let hashes = truncated_cdhashes(stderr);
+        assert_eq!(hashes.len(), 3);
+        assert!(hashes.iter().all(|hash| hash.len() == TRUNCATED_CDHASH_LEN));
+        assert_eq!(
+            hashes[0],
+            decode_hex("00112233445566778899aabbccddeeff00112233").unwrap()
+        );
+        assert_eq!(hashes[1], hashes[2]);
+    }
+
+    #[test]
+    fn no_cdhash_without_code_directory() {
+        let stderr =
+            "Executable=/tmp/foo\nCDHash=abc\nCDHash=zz112233445566778899aabbccddeeff00112233\n";
+        assert!(truncated_cdhashes(stderr).is_empty());
+        assert!(truncated_cdhashes("").is_empty());
+    }
+
+    #[test]
+    fn binds_ticket_to_cdhash() {
+        let bundle =
+            std::env::temp_dir().join(format!("whatsyoursign-ticket-{}.app", std::process::id()));
+        fs::create_dir_all(bundle.join("Contents")).unwrap();
+        let hash = "00112233445566778899aabbccddeeff00112233";
+        fs::write(
+            bundle.join("Contents").join("CodeResources"),
+            ticket(TICKET_VERSION, &decode_hex(hash).unwrap()),
+        )
+        .unwrap();
+
+        let bound = inspect(&bundle, &format!("CDHash={hash}\n")).unwrap();
+        let unbound =
+            inspect(&bundle, "CDHash=ffeeddccbbaa99887766554433221100ffeeddcc\n").unwrap();
+        fs::remove_dir_all(&bundle).unwrap();
+
+        assert!(bound.is_valid && bound.cdhash_bound && bound.problem.is_none());
+        assert!(!unbound.is_valid && !unbound.cdhash_bound);
+        assert_eq!(
+            unbound.problem.as_deref(),
+            Some("ticket does not cover this code directory hash")
+        );
+    }
+}